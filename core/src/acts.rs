@@ -0,0 +1,234 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Acts {
+    TitleScreen,
+    MainMenu,
+    TimeAttack,
+    PalmtreePanicAct1,
+    PalmtreePanicAct2,
+    PalmtreePanicAct3,
+    CollisionChaosAct1,
+    CollisionChaosAct2,
+    CollisionChaosAct3,
+    TidalTempestAct1,
+    TidalTempestAct2,
+    TidalTempestAct3,
+    QuartzQuadrantAct1,
+    QuartzQuadrantAct2,
+    QuartzQuadrantAct3,
+    WackyWorkbenchAct1,
+    WackyWorkbenchAct2,
+    WackyWorkbenchAct3,
+    StardustSpeedwayAct1,
+    StardustSpeedwayAct2,
+    StardustSpeedwayAct3,
+    MetallicMadnessAct1,
+    MetallicMadnessAct2,
+    MetallicMadnessAct3,
+    SpecialStage,
+    Credits,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Zone {
+    PalmtreePanic,
+    CollisionChaos,
+    TidalTempest,
+    QuartzQuadrant,
+    WackyWorkbench,
+    StardustSpeedway,
+    MetallicMadness,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TimePeriod {
+    Present,
+    Past,
+    GoodFuture,
+    BadFuture,
+}
+
+impl Zone {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PalmtreePanic => "Palmtree Panic",
+            Self::CollisionChaos => "Collision Chaos",
+            Self::TidalTempest => "Tidal Tempest",
+            Self::QuartzQuadrant => "Quartz Quadrant",
+            Self::WackyWorkbench => "Wacky Workbench",
+            Self::StardustSpeedway => "Stardust Speedway",
+            Self::MetallicMadness => "Metallic Madness",
+        }
+    }
+
+    /// Short name used by runners (eg. "PP" for Palmtree Panic)
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::PalmtreePanic => "PP",
+            Self::CollisionChaos => "CC",
+            Self::TidalTempest => "TT",
+            Self::QuartzQuadrant => "QQ",
+            Self::WackyWorkbench => "WW",
+            Self::StardustSpeedway => "SS",
+            Self::MetallicMadness => "MM",
+        }
+    }
+}
+
+impl TimePeriod {
+    pub fn is_future(self) -> bool {
+        matches!(self, Self::GoodFuture | Self::BadFuture)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Present => "Present",
+            Self::Past => "Past",
+            Self::GoodFuture => "Good Future",
+            Self::BadFuture => "Bad Future",
+        }
+    }
+}
+
+/// Where the player currently is: the act, plus the time period when inside a zone.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Location {
+    pub act: Acts,
+    pub time_period: Option<TimePeriod>,
+}
+
+impl Location {
+    /// Decodes the combined level id (`level_id_type * 100 + level_id`).
+    ///
+    /// Zones start at 100 and take 10 consecutive ids each: Act 1 and Act 2 exist in four variants
+    /// (Present, Past, Good Future, Bad Future), while Act 3 only has a Good and a Bad Future.
+    /// Special Stages start at 200.
+    pub fn from_level_id(lid: u32) -> Option<Self> {
+        let act = match lid {
+            0 => Acts::TitleScreen,
+            1 => Acts::MainMenu,
+            2 => Acts::TimeAttack,
+            8 => Acts::Credits,
+            200..=299 => Acts::SpecialStage,
+            100..=169 => {
+                let zone = ZONES[(lid - 100) as usize / 10];
+                let stage = (lid - 100) % 10;
+
+                let act = Acts::from_zone_act(zone, stage as u8 / 4 + 1)?;
+                let time_period = match stage {
+                    0 | 4 => TimePeriod::Present,
+                    1 | 5 => TimePeriod::Past,
+                    2 | 6 | 8 => TimePeriod::GoodFuture,
+                    _ => TimePeriod::BadFuture,
+                };
+
+                return Some(Self {
+                    act,
+                    time_period: Some(time_period),
+                });
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            act,
+            time_period: None,
+        })
+    }
+
+    pub fn zone(self) -> Option<Zone> {
+        self.act.zone()
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self {
+            act: Acts::PalmtreePanicAct1,
+            time_period: Some(TimePeriod::Present),
+        }
+    }
+}
+
+pub const ZONES: [Zone; 7] = [
+    Zone::PalmtreePanic,
+    Zone::CollisionChaos,
+    Zone::TidalTempest,
+    Zone::QuartzQuadrant,
+    Zone::WackyWorkbench,
+    Zone::StardustSpeedway,
+    Zone::MetallicMadness,
+];
+
+const ZONE_ACTS: [[Acts; 3]; 7] = [
+    [
+        Acts::PalmtreePanicAct1,
+        Acts::PalmtreePanicAct2,
+        Acts::PalmtreePanicAct3,
+    ],
+    [
+        Acts::CollisionChaosAct1,
+        Acts::CollisionChaosAct2,
+        Acts::CollisionChaosAct3,
+    ],
+    [
+        Acts::TidalTempestAct1,
+        Acts::TidalTempestAct2,
+        Acts::TidalTempestAct3,
+    ],
+    [
+        Acts::QuartzQuadrantAct1,
+        Acts::QuartzQuadrantAct2,
+        Acts::QuartzQuadrantAct3,
+    ],
+    [
+        Acts::WackyWorkbenchAct1,
+        Acts::WackyWorkbenchAct2,
+        Acts::WackyWorkbenchAct3,
+    ],
+    [
+        Acts::StardustSpeedwayAct1,
+        Acts::StardustSpeedwayAct2,
+        Acts::StardustSpeedwayAct3,
+    ],
+    [
+        Acts::MetallicMadnessAct1,
+        Acts::MetallicMadnessAct2,
+        Acts::MetallicMadnessAct3,
+    ],
+];
+
+impl Acts {
+    pub fn from_zone_act(zone: Zone, act: u8) -> Option<Self> {
+        ZONE_ACTS[zone as usize]
+            .get(act.checked_sub(1)? as usize)
+            .copied()
+    }
+
+    pub fn zone(self) -> Option<Zone> {
+        ZONES
+            .into_iter()
+            .zip(ZONE_ACTS)
+            .find_map(|(zone, acts)| acts.contains(&self).then_some(zone))
+    }
+
+    /// Name of the zone, or of the screen for anything outside a zone
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TitleScreen => "Title Screen",
+            Self::MainMenu => "Main Menu",
+            Self::TimeAttack => "Time Attack",
+            Self::SpecialStage => "Special Stage",
+            Self::Credits => "Credits",
+            act => act.zone().map_or("", Zone::name),
+        }
+    }
+
+    /// Act number (1 to 3) inside the zone
+    pub fn act_number(self) -> Option<u8> {
+        ZONE_ACTS.iter().find_map(|acts| {
+            acts.iter()
+                .position(|&act| act == self)
+                .map(|pos| pos as u8 + 1)
+        })
+    }
+}
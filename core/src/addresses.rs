@@ -0,0 +1,347 @@
+use asr::{
+    file_format::pe::{self, MachineType},
+    future::{next_tick, retry},
+    Address, Address32, Process,
+};
+
+use crate::{
+    game_version::GameVersion,
+    globals::GlobalVariables,
+    layout::{
+        generic_switch, layout, CentisecsBug, Field, Fields, Format, JumpTable, Layout, Operand,
+        Pointer,
+    },
+    trace::{Frame, LineBuffer},
+};
+
+pub struct Addresses {
+    pub demo_mode: Address,
+    pub state: Address,
+    pub score_tally_state: Address,
+    pub time_bonus: Address,
+    pub bhp_good: Address,
+    pub bhp_bad: Address,
+    pub level_id: Address,
+    pub level_id_type: Address,
+    pub timer_is_running: Address,
+    pub seconds: Address,
+    pub minutes: Address,
+    pub centisecs: Address,
+    /// Every global variable can be looked up by name
+    pub globals: GlobalVariables,
+    pub has_centisecs_bug: bool,
+    pub game_version: GameVersion,
+}
+
+impl Addresses {
+    pub async fn init(game: &Process, main_module_name: &str, heuristic_scan: bool) -> Self {
+        let main_module_base = retry(|| game.get_module_address(main_module_name)).await;
+
+        let main_module_size =
+            retry(|| pe::read_size_of_image(game, main_module_base)).await as u64;
+
+        let is_64_bit =
+            retry(|| pe::MachineType::read(game, main_module_base)).await == MachineType::X86_64;
+
+        let format = match is_64_bit {
+            false => Format::Pe32,
+            true => Format::Pe64,
+        };
+        let range = (main_module_base, main_module_size);
+
+        // Determine game version through signature scanning. Unknown versions are reported
+        // once the timeout expires, and then optionally looked for heuristically.
+        let mut ticks = 0;
+        // The name table is searched for once: the search is slow, so a miss is kept as well
+        let mut globals: Option<Option<GlobalVariables>> = None;
+        let (game_version, layout, variables) = loop {
+            if let Some((game_version, layout)) = detect(game, range, format) {
+                break (game_version, layout, None);
+            }
+
+            if ticks == DETECTION_TIMEOUT_TICKS {
+                report_unknown_version(main_module_name, format, main_module_size);
+            }
+
+            if heuristic_scan && ticks % DETECTION_TIMEOUT_TICKS == 0 && ticks > 0 {
+                asr::print_message("Trying to find the game's variables heuristically");
+                if globals.is_none() {
+                    let found = GlobalVariables::find(game).await;
+                    match &found {
+                        Some(found) => {
+                            let message = format_args!(
+                                "Found the global variables at 0x{:X}",
+                                found.values().value()
+                            );
+                            asr::print_message(LineBuffer::format(&message).as_str());
+                        }
+                        None => asr::print_message("Global variable names not found"),
+                    }
+                    globals = Some(found);
+                }
+                if let Some((game_version, layout, variables)) = scan_heuristically(
+                    game,
+                    main_module_base,
+                    range,
+                    format,
+                    globals.flatten().as_ref(),
+                ) {
+                    let message = format_args!(
+                        "Found the game's variables, using the {} layout",
+                        game_version.name()
+                    );
+                    asr::print_message(LineBuffer::format(&message).as_str());
+                    break (game_version, layout, Some(variables));
+                }
+            }
+
+            ticks += 1;
+            next_tick().await;
+        };
+
+        let has_centisecs_bug = match layout.centisecs_bug {
+            CentisecsBug::Always => true,
+            CentisecsBug::Never => false,
+            CentisecsBug::UnlessFound(scan) => scan(game, range).is_none(),
+        };
+
+        // Find addresses
+        let mut resolver = Resolver {
+            game,
+            main_module_base,
+            range,
+            jump_table: layout.jump_table,
+            variables: match variables {
+                Some(variables) => variables,
+                None => {
+                    retry(|| resolve_pointer(game, main_module_base, range, &layout.variables))
+                        .await
+                }
+            },
+            objects: match &layout.objects {
+                Some(objects) => {
+                    retry(|| resolve_pointer(game, main_module_base, range, objects)).await
+                }
+                None => Address::NULL,
+            },
+            globals: None,
+        };
+        let values = retry(|| resolver.field(&layout.globals)).await;
+        resolver.globals = Some(GlobalVariables::at(values));
+
+        retry(|| resolver.addresses(&layout.fields, has_centisecs_bug, game_version)).await
+    }
+}
+
+/// Ticks spent looking for a known version before reporting the game as unknown
+/// (about 10 seconds at the default tick rate)
+const DETECTION_TIMEOUT_TICKS: u32 = 1200;
+
+/// Maximum number of candidate jump tables checked by each heuristic scan
+const HEURISTIC_CANDIDATES: usize = 256;
+
+fn detect(game: &Process, range: (Address, u64), format: Format) -> Option<(GameVersion, Layout)> {
+    GameVersion::ALL.into_iter().find_map(|game_version| {
+        let layout = layout(game_version);
+        (layout.format == format && (layout.detect)(game, range).is_some())
+            .then_some((game_version, layout))
+    })
+}
+
+/// Prints everything needed to add support for an unknown build to the LiveSplit log.
+fn report_unknown_version(main_module_name: &str, format: Format, main_module_size: u64) {
+    let message = format_args!(
+        "Unknown game version: module {main_module_name}, {}, size 0x{main_module_size:X}",
+        format.name()
+    );
+    asr::print_message(LineBuffer::format(&message).as_str());
+
+    for game_version in GameVersion::ALL {
+        if layout(game_version).format == format {
+            let message = format_args!("Signature not found: {}", game_version.name());
+            asr::print_message(LineBuffer::format(&message).as_str());
+        }
+    }
+}
+
+/// Looks for the script variable jump table with a generic pattern, for builds whose exact
+/// signatures are unknown. Each candidate is tried with the layouts of every known version of
+/// the same format, until all fields resolve to plausible values.
+///
+/// When the global variables could be found from their names, a candidate is only accepted if
+/// it reads them from the same place, instead of only relying on plausible values.
+fn scan_heuristically(
+    game: &Process,
+    main_module_base: Address,
+    range: (Address, u64),
+    format: Format,
+    globals: Option<&GlobalVariables>,
+) -> Option<(GameVersion, Layout, Address)> {
+    let switch = generic_switch(format)?;
+    let end = range.0 + range.1;
+    let mut start = range.0;
+
+    let mut candidates = [Address::NULL; HEURISTIC_CANDIDATES];
+    let mut len = 0;
+    while len < HEURISTIC_CANDIDATES {
+        let Some(size) = end.value().checked_sub(start.value()) else {
+            break;
+        };
+        let Some(found) = (switch.scan)(game, (start, size)) else {
+            break;
+        };
+        start = found + 1u64;
+
+        if let Some(variables) = read_operand(
+            game,
+            main_module_base,
+            found + switch.offset,
+            switch.operand,
+        ) {
+            candidates[len] = variables;
+            len += 1;
+        }
+    }
+    let candidates = &candidates[..len];
+
+    for game_version in GameVersion::ALL {
+        let layout = layout(game_version);
+        if layout.format != format {
+            continue;
+        }
+
+        // Everything not read through the variable switch is the same for every candidate
+        let objects = match &layout.objects {
+            Some(objects) => match resolve_pointer(game, main_module_base, range, objects) {
+                Some(objects) => objects,
+                None => continue,
+            },
+            None => Address::NULL,
+        };
+        let mut resolver = Resolver {
+            game,
+            main_module_base,
+            range,
+            jump_table: layout.jump_table,
+            variables: Address::NULL,
+            objects,
+            globals: None,
+        };
+        let Some(fields) = layout.fields.try_map(|field| match field {
+            Field::Code(_) => resolver.field(field).map(Field::Resolved),
+            field => Some(*field),
+        }) else {
+            continue;
+        };
+
+        for &variables in candidates {
+            resolver.variables = variables;
+            resolver.globals = None;
+            let Some(values) = resolver.field(&layout.globals) else {
+                continue;
+            };
+            if globals.is_some_and(|globals| globals.values() != values) {
+                continue;
+            }
+            resolver.globals = Some(GlobalVariables::at(values));
+
+            if resolver
+                .addresses(&fields, false, game_version)
+                .is_some_and(|addresses| Frame::read(game, &addresses).is_plausible())
+            {
+                return Some((game_version, layout, variables));
+            }
+        }
+    }
+
+    None
+}
+
+/// Resolves the fields of a `Layout`, once its base pointers have been found.
+struct Resolver<'a> {
+    game: &'a Process,
+    main_module_base: Address,
+    range: (Address, u64),
+    jump_table: JumpTable,
+    variables: Address,
+    objects: Address,
+    globals: Option<GlobalVariables>,
+}
+
+impl Resolver<'_> {
+    fn addresses(
+        &self,
+        fields: &Fields,
+        has_centisecs_bug: bool,
+        game_version: GameVersion,
+    ) -> Option<Addresses> {
+        Some(Addresses {
+            demo_mode: self.field(&fields.demo_mode)?,
+            state: self.field(&fields.state)?,
+            score_tally_state: self.field(&fields.score_tally_state)?,
+            time_bonus: self.field(&fields.time_bonus)?,
+            bhp_good: self.field(&fields.bhp_good)?,
+            bhp_bad: self.field(&fields.bhp_bad)?,
+            level_id: self.field(&fields.level_id)?,
+            level_id_type: self.field(&fields.level_id_type)?,
+            timer_is_running: self.field(&fields.timer_is_running)?,
+            seconds: self.field(&fields.seconds)?,
+            minutes: self.field(&fields.minutes)?,
+            centisecs: self.field(&fields.centisecs)?,
+            globals: self.globals?,
+            has_centisecs_bug,
+            game_version,
+        })
+    }
+
+    fn field(&self, field: &Field) -> Option<Address> {
+        match *field {
+            Field::Variable {
+                case,
+                operand,
+                encoding,
+                offset,
+            } => {
+                let entry = self.variables + case;
+                let case = match self.jump_table {
+                    JumpTable::Absolute32 => self.game.read::<Address32>(entry).ok()?.into(),
+                    JumpTable::ModuleRelative => {
+                        self.main_module_base + self.game.read::<u32>(entry).ok()?
+                    }
+                };
+                Some(
+                    read_operand(self.game, self.main_module_base, case + operand, encoding)?
+                        + offset,
+                )
+            }
+            Field::Object(offset) => Some(self.objects + offset),
+            Field::Resolved(address) => Some(address),
+            Field::Code(pointers) => pointers.iter().find_map(|pointer| {
+                resolve_pointer(self.game, self.main_module_base, self.range, pointer)
+            }),
+        }
+    }
+}
+
+fn resolve_pointer(
+    game: &Process,
+    main_module_base: Address,
+    range: (Address, u64),
+    pointer: &Pointer,
+) -> Option<Address> {
+    let at = (pointer.scan)(game, range)? + pointer.offset;
+    read_operand(game, main_module_base, at, pointer.operand)
+}
+
+fn read_operand(
+    game: &Process,
+    main_module_base: Address,
+    at: Address,
+    operand: Operand,
+) -> Option<Address> {
+    match operand {
+        Operand::Absolute32 => Some(game.read::<Address32>(at).ok()?.into()),
+        Operand::ModuleRelative => Some(main_module_base + game.read::<u32>(at).ok()?),
+        Operand::RipRelative => Some(at + 0x4 + game.read::<i32>(at).ok()?),
+    }
+}
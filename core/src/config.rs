@@ -0,0 +1,270 @@
+use crate::{Acts, Zone};
+
+/// Speedrun categories, each with its own start, split, timing and final split rules
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Category {
+    /// Main menu start, act splits, IGT
+    #[default]
+    AnyPercent,
+    /// Any% rules, plus the Good Future splits and final split
+    GoodFuture,
+    /// Any% splits, timed with RTA-TB
+    AllTimeStones,
+    /// Good Future and All Time Stones rules combined
+    HundredPercent,
+    /// Time Attack start, split and reset
+    IndividualLevel,
+}
+
+impl Category {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AnyPercent => "Any%",
+            Self::GoodFuture => "Good Future",
+            Self::AllTimeStones => "All Time Stones",
+            Self::HundredPercent => "100%",
+            Self::IndividualLevel => "Individual Level",
+        }
+    }
+
+    /// Time Attack start, split and reset, instead of the full game rules
+    pub fn individual_level(self) -> bool {
+        self == Self::IndividualLevel
+    }
+
+    /// Act 3s played in the Bad Future don't split, and the final split requires the
+    /// Good Future boss
+    pub fn good_future(self) -> bool {
+        matches!(self, Self::GoodFuture | Self::HundredPercent)
+    }
+
+    /// Timed with RTA-TB instead of IGT, with the final split once the credits or the
+    /// main menu load
+    pub fn rta_tb(self) -> bool {
+        matches!(self, Self::AllTimeStones | Self::HundredPercent)
+    }
+}
+
+/// The user settings the splitting logic depends on.
+///
+/// The autosplitter fills this in from its settings GUI every tick, while native tools
+/// (eg. the trace replay tool) can simply create one.
+#[derive(Clone, Copy)]
+pub struct Config {
+    /// Sets the start, timing and final split rules. Everything below only turns
+    /// individual splits and rules on or off.
+    pub category: Category,
+    pub start: bool,
+    pub reset: bool,
+    pub palmtree_panic_restored: bool,
+    pub collision_chaos_restored: bool,
+    pub tidal_tempest_restored: bool,
+    pub quartz_quadrant_restored: bool,
+    pub wacky_workbench_restored: bool,
+    pub stardust_speedway_restored: bool,
+    pub metallic_madness_restored: bool,
+    pub split_on_tally: bool,
+    pub palmtree_panic_1: bool,
+    pub palmtree_panic_2: bool,
+    pub palmtree_panic_3: bool,
+    pub collision_chaos_1: bool,
+    pub collision_chaos_2: bool,
+    pub collision_chaos_3: bool,
+    pub tidal_tempest_1: bool,
+    pub tidal_tempest_2: bool,
+    pub tidal_tempest_3: bool,
+    pub quartz_quadrant_1: bool,
+    pub quartz_quadrant_2: bool,
+    pub quartz_quadrant_3: bool,
+    pub wacky_workbench_1: bool,
+    pub wacky_workbench_2: bool,
+    pub wacky_workbench_3: bool,
+    pub stardust_speedway_1: bool,
+    pub stardust_speedway_2: bool,
+    pub stardust_speedway_3: bool,
+    pub metallic_madness_1: bool,
+    pub metallic_madness_2: bool,
+    pub metallic_madness_3: bool,
+    pub palmtree_panic_1_past: bool,
+    pub palmtree_panic_1_future: bool,
+    pub palmtree_panic_2_past: bool,
+    pub palmtree_panic_2_future: bool,
+    pub collision_chaos_1_past: bool,
+    pub collision_chaos_1_future: bool,
+    pub collision_chaos_2_past: bool,
+    pub collision_chaos_2_future: bool,
+    pub tidal_tempest_1_past: bool,
+    pub tidal_tempest_1_future: bool,
+    pub tidal_tempest_2_past: bool,
+    pub tidal_tempest_2_future: bool,
+    pub quartz_quadrant_1_past: bool,
+    pub quartz_quadrant_1_future: bool,
+    pub quartz_quadrant_2_past: bool,
+    pub quartz_quadrant_2_future: bool,
+    pub wacky_workbench_1_past: bool,
+    pub wacky_workbench_1_future: bool,
+    pub wacky_workbench_2_past: bool,
+    pub wacky_workbench_2_future: bool,
+    pub stardust_speedway_1_past: bool,
+    pub stardust_speedway_1_future: bool,
+    pub stardust_speedway_2_past: bool,
+    pub stardust_speedway_2_future: bool,
+    pub metallic_madness_1_past: bool,
+    pub metallic_madness_1_future: bool,
+    pub metallic_madness_2_past: bool,
+    pub metallic_madness_2_future: bool,
+}
+
+impl Default for Config {
+    /// Same values as the defaults in the autosplitter's settings GUI
+    fn default() -> Self {
+        Self {
+            category: Category::AnyPercent,
+            start: true,
+            reset: true,
+            palmtree_panic_restored: true,
+            collision_chaos_restored: true,
+            tidal_tempest_restored: true,
+            quartz_quadrant_restored: true,
+            wacky_workbench_restored: true,
+            stardust_speedway_restored: true,
+            metallic_madness_restored: true,
+            split_on_tally: false,
+            palmtree_panic_1: true,
+            palmtree_panic_2: true,
+            palmtree_panic_3: true,
+            collision_chaos_1: true,
+            collision_chaos_2: true,
+            collision_chaos_3: true,
+            tidal_tempest_1: true,
+            tidal_tempest_2: true,
+            tidal_tempest_3: true,
+            quartz_quadrant_1: true,
+            quartz_quadrant_2: true,
+            quartz_quadrant_3: true,
+            wacky_workbench_1: true,
+            wacky_workbench_2: true,
+            wacky_workbench_3: true,
+            stardust_speedway_1: true,
+            stardust_speedway_2: true,
+            stardust_speedway_3: true,
+            metallic_madness_1: true,
+            metallic_madness_2: true,
+            metallic_madness_3: true,
+            palmtree_panic_1_past: false,
+            palmtree_panic_1_future: false,
+            palmtree_panic_2_past: false,
+            palmtree_panic_2_future: false,
+            collision_chaos_1_past: false,
+            collision_chaos_1_future: false,
+            collision_chaos_2_past: false,
+            collision_chaos_2_future: false,
+            tidal_tempest_1_past: false,
+            tidal_tempest_1_future: false,
+            tidal_tempest_2_past: false,
+            tidal_tempest_2_future: false,
+            quartz_quadrant_1_past: false,
+            quartz_quadrant_1_future: false,
+            quartz_quadrant_2_past: false,
+            quartz_quadrant_2_future: false,
+            wacky_workbench_1_past: false,
+            wacky_workbench_1_future: false,
+            wacky_workbench_2_past: false,
+            wacky_workbench_2_future: false,
+            stardust_speedway_1_past: false,
+            stardust_speedway_1_future: false,
+            stardust_speedway_2_past: false,
+            stardust_speedway_2_future: false,
+            metallic_madness_1_past: false,
+            metallic_madness_1_future: false,
+            metallic_madness_2_past: false,
+            metallic_madness_2_future: false,
+        }
+    }
+}
+
+impl Config {
+    /// Whether to split at the end of the given act
+    pub fn act(&self, act: Acts) -> bool {
+        match act {
+            Acts::PalmtreePanicAct1 => self.palmtree_panic_1,
+            Acts::PalmtreePanicAct2 => self.palmtree_panic_2,
+            Acts::PalmtreePanicAct3 => self.palmtree_panic_3,
+            Acts::CollisionChaosAct1 => self.collision_chaos_1,
+            Acts::CollisionChaosAct2 => self.collision_chaos_2,
+            Acts::CollisionChaosAct3 => self.collision_chaos_3,
+            Acts::TidalTempestAct1 => self.tidal_tempest_1,
+            Acts::TidalTempestAct2 => self.tidal_tempest_2,
+            Acts::TidalTempestAct3 => self.tidal_tempest_3,
+            Acts::QuartzQuadrantAct1 => self.quartz_quadrant_1,
+            Acts::QuartzQuadrantAct2 => self.quartz_quadrant_2,
+            Acts::QuartzQuadrantAct3 => self.quartz_quadrant_3,
+            Acts::WackyWorkbenchAct1 => self.wacky_workbench_1,
+            Acts::WackyWorkbenchAct2 => self.wacky_workbench_2,
+            Acts::WackyWorkbenchAct3 => self.wacky_workbench_3,
+            Acts::StardustSpeedwayAct1 => self.stardust_speedway_1,
+            Acts::StardustSpeedwayAct2 => self.stardust_speedway_2,
+            Acts::StardustSpeedwayAct3 => self.stardust_speedway_3,
+            Acts::MetallicMadnessAct1 => self.metallic_madness_1,
+            Acts::MetallicMadnessAct2 => self.metallic_madness_2,
+            Acts::MetallicMadnessAct3 => self.metallic_madness_3,
+            _ => false,
+        }
+    }
+
+    /// Whether to split when warping to the Past inside the given act
+    pub fn time_travel_past(&self, act: Acts) -> bool {
+        match act {
+            Acts::PalmtreePanicAct1 => self.palmtree_panic_1_past,
+            Acts::PalmtreePanicAct2 => self.palmtree_panic_2_past,
+            Acts::CollisionChaosAct1 => self.collision_chaos_1_past,
+            Acts::CollisionChaosAct2 => self.collision_chaos_2_past,
+            Acts::TidalTempestAct1 => self.tidal_tempest_1_past,
+            Acts::TidalTempestAct2 => self.tidal_tempest_2_past,
+            Acts::QuartzQuadrantAct1 => self.quartz_quadrant_1_past,
+            Acts::QuartzQuadrantAct2 => self.quartz_quadrant_2_past,
+            Acts::WackyWorkbenchAct1 => self.wacky_workbench_1_past,
+            Acts::WackyWorkbenchAct2 => self.wacky_workbench_2_past,
+            Acts::StardustSpeedwayAct1 => self.stardust_speedway_1_past,
+            Acts::StardustSpeedwayAct2 => self.stardust_speedway_2_past,
+            Acts::MetallicMadnessAct1 => self.metallic_madness_1_past,
+            Acts::MetallicMadnessAct2 => self.metallic_madness_2_past,
+            _ => false,
+        }
+    }
+
+    /// Whether to split when warping to the Future inside the given act
+    pub fn time_travel_future(&self, act: Acts) -> bool {
+        match act {
+            Acts::PalmtreePanicAct1 => self.palmtree_panic_1_future,
+            Acts::PalmtreePanicAct2 => self.palmtree_panic_2_future,
+            Acts::CollisionChaosAct1 => self.collision_chaos_1_future,
+            Acts::CollisionChaosAct2 => self.collision_chaos_2_future,
+            Acts::TidalTempestAct1 => self.tidal_tempest_1_future,
+            Acts::TidalTempestAct2 => self.tidal_tempest_2_future,
+            Acts::QuartzQuadrantAct1 => self.quartz_quadrant_1_future,
+            Acts::QuartzQuadrantAct2 => self.quartz_quadrant_2_future,
+            Acts::WackyWorkbenchAct1 => self.wacky_workbench_1_future,
+            Acts::WackyWorkbenchAct2 => self.wacky_workbench_2_future,
+            Acts::StardustSpeedwayAct1 => self.stardust_speedway_1_future,
+            Acts::StardustSpeedwayAct2 => self.stardust_speedway_2_future,
+            Acts::MetallicMadnessAct1 => self.metallic_madness_1_future,
+            Acts::MetallicMadnessAct2 => self.metallic_madness_2_future,
+            _ => false,
+        }
+    }
+
+    /// Whether to split when reaching the Good Future of the given zone's Act 3, instead of
+    /// splitting at the end of its Act 2
+    pub fn future_restored(&self, zone: Zone) -> bool {
+        match zone {
+            Zone::PalmtreePanic => self.palmtree_panic_restored,
+            Zone::CollisionChaos => self.collision_chaos_restored,
+            Zone::TidalTempest => self.tidal_tempest_restored,
+            Zone::QuartzQuadrant => self.quartz_quadrant_restored,
+            Zone::WackyWorkbench => self.wacky_workbench_restored,
+            Zone::StardustSpeedway => self.stardust_speedway_restored,
+            Zone::MetallicMadness => self.metallic_madness_restored,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use asr::{signature::Signature, Process};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameVersion {
+    Retail,
+    Decompilation32bit1_0_0, // Valid from base version up tp v1.3.0)
+    Decompilation32bit1_3_1, // Valid from v1.3.1 onwards
+    Decompilation64bit1_0_0,
+    Decompilation64bit1_3_1,
+    Decompilation64bit1_3_2, // Valid from v1.3.2 64bit onwards
+}
+
+impl GameVersion {
+    /// Every supported version, in the order they are detected
+    pub const ALL: [Self; 6] = [
+        Self::Retail,
+        Self::Decompilation32bit1_0_0,
+        Self::Decompilation32bit1_3_1,
+        Self::Decompilation64bit1_0_0,
+        Self::Decompilation64bit1_3_1,
+        Self::Decompilation64bit1_3_2,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Retail => "Retail",
+            Self::Decompilation32bit1_0_0 => "Decompilation 32-bit (v1.0.0)",
+            Self::Decompilation32bit1_3_1 => "Decompilation 32-bit (v1.3.1)",
+            Self::Decompilation64bit1_0_0 => "Decompilation 64-bit (v1.0.0)",
+            Self::Decompilation64bit1_3_1 => "Decompilation 64-bit (v1.3.1)",
+            Self::Decompilation64bit1_3_2 => "Decompilation 64-bit (v1.3.2)",
+        }
+    }
+}
+
+/// Windows executables of the game, found as well when they run under Wine or Proton.
+/// Native Linux builds of the decompilation are not attached to: no signature has been checked
+/// against one yet (see the README).
+pub const PROCESS_NAMES: &[&str] = &[
+    "soniccd.exe",
+    "RSDKv3.exe",
+    "RSDKv3_64.exe",
+    "RSDKv3_HW.exe",
+    "RSDKv3_HW_64.exe",
+    "Sonic CD.exe",
+    "Sonic CD_64.exe",
+    "Restored.exe",
+    "Legacy.exe",
+];
+
+/// Linux truncates process names to 15 characters, so under Wine and Proton
+/// the longer Windows executable names have to be looked up truncated.
+const LINUX_PROCESS_NAME_LEN: usize = 15;
+
+/// Attaches to the first running game process.
+///
+/// Returns the process along with the name of its main module, which is always the full
+/// executable name, even when the process itself was found by its truncated name.
+pub fn attach() -> Option<(&'static str, Process)> {
+    PROCESS_NAMES.iter().find_map(|&name| {
+        let process = Process::attach(name)
+            .or_else(|| Process::attach(name.get(..LINUX_PROCESS_NAME_LEN)?))?;
+        Some((name, process))
+    })
+}
+
+pub(crate) const SIG32_RETAIL: Signature<13> =
+    Signature::new("FF 24 85 ?? ?? ?? ?? 8B 4D F0 8B 14 8D");
+pub(crate) const SIG32_RETAIL_CENTISECS: Signature<15> =
+    Signature::new("A2 ?? ?? ?? ?? 0F B6 0D ?? ?? ?? ?? 83 F9 3C");
+
+pub(crate) const SIG32_DECOMP_1_0_0: Signature<10> =
+    Signature::new("FF 24 85 ?? ?? ?? ?? 8B 04 B5");
+pub(crate) const SIG32_DECOMP_1_3_1: Signature<10> =
+    Signature::new("FF 24 8D ?? ?? ?? ?? 8B 0C 85");
+pub(crate) const SIG32_DECOMP_CENTISECS: Signature<8> = Signature::new("89 0D ?? ?? ?? ?? 3B CE");
+pub(crate) const SIG32_DECOMP_TIMERBUG: Signature<34> = Signature::new("C6 05 ?? ?? ?? ?? 00 C6 05 ?? ?? ?? ?? 00 C7 05 ?? ?? ?? ?? 00 00 00 00 C7 05 ?? ?? ?? ?? 00 00 00 00");
+
+pub(crate) const SIG64_DECOMP_1_0_0: Signature<11> =
+    Signature::new("41 8B 8C 8C ?? ?? ?? ?? 49 03 CC");
+pub(crate) const SIG64_DECOMP_1_3_1: Signature<9> = Signature::new("41 8B 94 95 ?? ?? ?? ?? 49");
+pub(crate) const SIG64_DECOMP_1_3_2: Signature<9> = Signature::new("41 8B 94 92 ?? ?? ?? ?? 49");
+pub(crate) const SIG64_DECOMP_1_0_0_LEA: Signature<10> =
+    Signature::new("4C 8D 35 ?? ?? ?? ?? 44 8B 1D"); // Signature::new("4C 8D 35 ?? ?? ?? ?? 66 90");
+pub(crate) const SIG64_DECOMP_CENTISECS: Signature<11> =
+    Signature::new("89 0D ?? ?? ?? ?? 41 3B C8 75 3A");
+pub(crate) const SIG64_DECOMP_CENTISECS_ALT: Signature<11> =
+    Signature::new("89 0D ?? ?? ?? ?? 41 3B C8 75 3E");
+pub(crate) const SIG64_DECOMP_TIMERBUG: Signature<14> =
+    Signature::new("89 15 ?? ?? ?? ?? E8 ?? ?? ?? ?? 48 63 15");
+
+// Generic forms of the variable switch patterns above, used by the heuristic scan
+pub(crate) const SIG32_GENERIC_SWITCH: Signature<7> = Signature::new("FF 24 ?? ?? ?? ?? ??");
+pub(crate) const SIG64_GENERIC_SWITCH: Signature<9> = Signature::new("41 8B ?? ?? ?? ?? ?? ?? 49");
@@ -0,0 +1,146 @@
+use asr::{future::next_tick, Address, Process};
+
+/// Number of global variables the engine has room for (`GLOBALVAR_COUNT`)
+const GLOBALVAR_COUNT: u64 = 0x100;
+
+/// Size of each entry of the name table
+const NAME_SIZE: u64 = 0x20;
+
+/// Size of the chunks memory is searched in
+const CHUNK_SIZE: usize = 0x1000;
+
+/// Chunks searched on each tick, so the search doesn't hold up the autosplitter (1 MiB)
+const CHUNKS_PER_TICK: u32 = 0x100;
+
+/// Consecutive entries looked for in a chunk before checking the whole table. The names
+/// themselves differ between mods, so the table is only recognized by its layout.
+const CHECKED_NAMES: usize = 4;
+
+/// Entries a name table must have to be accepted. A few strings spaced like its entries could
+/// appear anywhere, but only the table has so many names in a row.
+const MIN_NAMES: u64 = 16;
+
+/// The engine's global variables, found structurally rather than through code signatures.
+///
+/// The names of the global variables are loaded from the game data at startup into a table of
+/// fixed size strings, right after the table holding their values:
+/// `int globalVariables[GLOBALVAR_COUNT]; char globalVariableNames[GLOBALVAR_COUNT][0x20];`
+/// Both tables are the same in every build, however it was compiled.
+///
+/// The game only fills the name table once it has read its scripts, which can be after it has
+/// been attached to, so names are read again on every lookup.
+#[derive(Clone, Copy, Debug)]
+pub struct GlobalVariables {
+    values: Address,
+    names: Address,
+}
+
+impl GlobalVariables {
+    /// The global variables whose values start at the given address
+    pub fn at(values: Address) -> Self {
+        Self {
+            values,
+            names: values + GLOBALVAR_COUNT * 4,
+        }
+    }
+
+    /// Searches the whole process memory for the name table, a few chunks every tick. This is
+    /// slow, so it should only be done when no signature matches.
+    pub async fn find(game: &Process) -> Option<Self> {
+        let mut buf = [0; CHUNK_SIZE];
+        let mut chunks = 0;
+
+        for (address, size) in game.memory_ranges().filter_map(|range| range.range().ok()) {
+            let mut offset = 0;
+            while offset < size {
+                let len = (size - offset).min(CHUNK_SIZE as u64) as usize;
+                let chunk = &mut buf[..len];
+                if game.read_into_buf(address + offset, chunk).is_ok() {
+                    if let Some(globals) = find_in_chunk(game, address + offset, chunk) {
+                        return Some(globals);
+                    }
+                }
+                // Overlap the chunks, so entries across chunk boundaries are found as well
+                offset += (CHUNK_SIZE - CHECKED_NAMES * NAME_SIZE as usize - 1) as u64;
+
+                chunks += 1;
+                if chunks % CHUNKS_PER_TICK == 0 {
+                    next_tick().await;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Address of the value of the given global variable, if it is defined by the game scripts
+    pub fn address_of(&self, game: &Process, name: &str) -> Option<Address> {
+        (0..GLOBALVAR_COUNT)
+            .map_while(|index| Some((index, read_name(game, self.names + index * NAME_SIZE)?)))
+            // Like the engine, names are compared case insensitively
+            .find(|(_, (bytes, len))| bytes[..*len].eq_ignore_ascii_case(name.as_bytes()))
+            .map(|(index, _)| self.address_at(index))
+    }
+
+    /// Number of names in the name table, none until the game has loaded them
+    pub fn count(&self, game: &Process) -> u64 {
+        (0..GLOBALVAR_COUNT)
+            .take_while(|&index| read_name(game, self.names + index * NAME_SIZE).is_some())
+            .count() as u64
+    }
+
+    /// Start of the table holding the values of the global variables
+    pub fn values(&self) -> Address {
+        self.values
+    }
+
+    /// Address of the value of the global variable at the given index
+    pub fn address_at(&self, index: u64) -> Address {
+        self.values + index * 4
+    }
+}
+
+/// Looks for consecutive name table entries in a chunk read at `address`, then for the whole
+/// table around them. Every entry starts right after the padding of the previous one.
+fn find_in_chunk(game: &Process, address: Address, chunk: &[u8]) -> Option<GlobalVariables> {
+    (1..chunk.len()).find_map(|position| {
+        let entries = chunk[position..].chunks_exact(NAME_SIZE as usize);
+        (chunk[position - 1] == 0
+            && entries.len() >= CHECKED_NAMES
+            && entries
+                .take(CHECKED_NAMES)
+                .all(|entry| name_len(entry).is_some()))
+        .then(|| from_entry(game, address + position as u64))?
+    })
+}
+
+/// Walks the name table back to its start from one of its entries, then counts its entries.
+/// Fails unless the table has at least `MIN_NAMES` entries and its values can be read.
+fn from_entry(game: &Process, entry: Address) -> Option<GlobalVariables> {
+    let mut names = entry;
+    for _ in 0..GLOBALVAR_COUNT {
+        let previous = Address::new(names.value().checked_sub(NAME_SIZE)?);
+        if read_name(game, previous).is_none() {
+            break;
+        }
+        names = previous;
+    }
+
+    let globals = GlobalVariables::at(Address::new(
+        names.value().checked_sub(GLOBALVAR_COUNT * 4)?,
+    ));
+    (globals.count(game) >= MIN_NAMES && game.read::<u32>(globals.values).is_ok())
+        .then_some(globals)
+}
+
+/// A name table entry read from the game.
+fn read_name(game: &Process, entry: Address) -> Option<([u8; NAME_SIZE as usize], usize)> {
+    let bytes = game.read::<[u8; NAME_SIZE as usize]>(entry).ok()?;
+    Some((bytes, name_len(&bytes)?))
+}
+
+/// Length of the name in a name table entry: printable characters, terminated within the entry.
+fn name_len(entry: &[u8]) -> Option<usize> {
+    let len = entry.iter().position(|&b| b == 0)?;
+    (len > 0 && entry[..len].iter().all(u8::is_ascii_graphic)).then_some(len)
+}
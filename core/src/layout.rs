@@ -0,0 +1,482 @@
+//! Where every value lives in memory, for each supported game version.
+//!
+//! `Addresses::init` only interprets these tables, so supporting a new build is a matter of
+//! adding its signatures to `game_version` and describing its layout here.
+
+use asr::{Address, Process};
+
+use crate::game_version::*;
+
+/// Signature scan over the main module, returning the start of the match.
+pub(crate) type Scan = fn(&Process, (Address, u64)) -> Option<Address>;
+
+macro_rules! scan {
+    ($($signature:expr),+) => {
+        |game, range| None $(.or_else(|| $signature.scan_process_range(game, range)))+
+    };
+}
+
+/// Executable format and architecture of the main module.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Format {
+    Pe32,
+    Pe64,
+}
+
+impl Format {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pe32 => "PE 32-bit",
+            Self::Pe64 => "PE 64-bit",
+        }
+    }
+}
+
+/// How a 32-bit operand encodes the address it refers to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Operand {
+    /// Absolute address
+    Absolute32,
+    /// Offset from the start of the main module
+    ModuleRelative,
+    /// Displacement from the end of the operand (x64 RIP-relative addressing)
+    RipRelative,
+}
+
+/// How the entries of the script variable jump table encode the address of each case.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum JumpTable {
+    Absolute32,
+    ModuleRelative,
+}
+
+/// An address taken from an instruction operand, found by signature.
+#[derive(Clone, Copy)]
+pub(crate) struct Pointer {
+    pub scan: Scan,
+    /// Offset of the operand from the start of the signature
+    pub offset: u32,
+    pub operand: Operand,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Field {
+    /// Value read by a case of the script variable switch: the operand at `operand` bytes into
+    /// the code of the case, plus `offset`. Cases are given as offsets in the jump table.
+    Variable {
+        case: u32,
+        operand: u32,
+        encoding: Operand,
+        offset: u32,
+    },
+    /// Offset into the object entity list
+    Object(u32),
+    /// Operand of an instruction found by its own signature. The first pointer found is used.
+    Code(&'static [Pointer]),
+    /// Address already resolved, so it isn't scanned for again
+    Resolved(Address),
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum CentisecsBug {
+    Always,
+    Never,
+    /// The game has the bug unless the code fixing it is found
+    UnlessFound(Scan),
+}
+
+/// Every field of `Addresses`, as resolved for one game version.
+#[derive(Clone, Copy)]
+pub(crate) struct Fields {
+    pub demo_mode: Field,
+    pub state: Field,
+    pub score_tally_state: Field,
+    pub time_bonus: Field,
+    pub bhp_good: Field,
+    pub bhp_bad: Field,
+    pub level_id: Field,
+    pub level_id_type: Field,
+    pub timer_is_running: Field,
+    pub seconds: Field,
+    pub minutes: Field,
+    pub centisecs: Field,
+}
+
+impl Fields {
+    /// Maps every field, failing if any of them fails
+    pub fn try_map(&self, mut f: impl FnMut(&Field) -> Option<Field>) -> Option<Self> {
+        Some(Self {
+            demo_mode: f(&self.demo_mode)?,
+            state: f(&self.state)?,
+            score_tally_state: f(&self.score_tally_state)?,
+            time_bonus: f(&self.time_bonus)?,
+            bhp_good: f(&self.bhp_good)?,
+            bhp_bad: f(&self.bhp_bad)?,
+            level_id: f(&self.level_id)?,
+            level_id_type: f(&self.level_id_type)?,
+            timer_is_running: f(&self.timer_is_running)?,
+            seconds: f(&self.seconds)?,
+            minutes: f(&self.minutes)?,
+            centisecs: f(&self.centisecs)?,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn all(&self) -> [&Field; 12] {
+        [
+            &self.demo_mode,
+            &self.state,
+            &self.score_tally_state,
+            &self.time_bonus,
+            &self.bhp_good,
+            &self.bhp_bad,
+            &self.level_id,
+            &self.level_id_type,
+            &self.timer_is_running,
+            &self.seconds,
+            &self.minutes,
+            &self.centisecs,
+        ]
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Layout {
+    pub format: Format,
+    /// Matches only on this version. Versions are tried in `GameVersion::ALL` order.
+    pub detect: Scan,
+    pub centisecs_bug: CentisecsBug,
+    /// Jump table of the script variable switch
+    pub variables: Pointer,
+    pub jump_table: JumpTable,
+    /// Object entity list, when object fields are not read through the variable switch
+    pub objects: Option<Pointer>,
+    /// Start of the global variable values
+    pub globals: Field,
+    pub fields: Fields,
+}
+
+const fn variable(case: u32, operand: u32, encoding: Operand, offset: u32) -> Field {
+    Field::Variable {
+        case,
+        operand,
+        encoding,
+        offset,
+    }
+}
+
+/// 32-bit builds read everything, object values included, through the variable switch
+const fn fields32(stage: u32, objects: u32, globals: u32, timer: [Field; 3]) -> Fields {
+    use Operand::Absolute32;
+
+    Fields {
+        demo_mode: variable(0x4 * 11, globals, Absolute32, 0x1AC),
+        state: variable(0x4 * 19, objects, Absolute32, 0x1078),
+        score_tally_state: variable(0x4 * 19, objects, Absolute32, 0x7F8),
+        time_bonus: variable(0x4 * 37, objects, Absolute32, 0x7F8),
+        bhp_good: variable(0x4 * 32, objects, Absolute32, 0x37C8),
+        bhp_bad: variable(0x4 * 32, objects, Absolute32, 0x380C),
+        level_id: variable(0x4 * 120, stage, Absolute32, 0),
+        level_id_type: variable(0x4 * 119, stage, Absolute32, 0),
+        timer_is_running: variable(0x4 * 121, 11, Absolute32, 0),
+        centisecs: timer[0],
+        seconds: timer[1],
+        minutes: timer[2],
+    }
+}
+
+/// 64-bit builds share the same object layout, with the object entity list found separately
+const fn fields64(
+    stage: (u32, Operand),
+    timer_is_running: (u32, Operand),
+    globals: (u32, Operand),
+    timer: [Field; 3],
+) -> Fields {
+    Fields {
+        demo_mode: variable(0x4 * 11, globals.0, globals.1, 0x1AC),
+        state: Field::Object(0x10B2),
+        score_tally_state: Field::Object(0x832),
+        time_bonus: Field::Object(0x814),
+        bhp_good: Field::Object(0x37D0),
+        bhp_bad: Field::Object(0x3814),
+        level_id: variable(0x4 * 120, stage.0, stage.1, 0),
+        level_id_type: variable(0x4 * 119, stage.0, stage.1, 0),
+        timer_is_running: variable(0x4 * 121, timer_is_running.0, timer_is_running.1, 0),
+        centisecs: timer[0],
+        seconds: timer[1],
+        minutes: timer[2],
+    }
+}
+
+const fn code(scan: Scan, offset: u32, operand: Operand) -> Pointer {
+    Pointer {
+        scan,
+        offset,
+        operand,
+    }
+}
+
+/// Stage timer (centiseconds, seconds, minutes) as written by the code updating it
+const TIMER32_RETAIL: [Field; 3] = [
+    Field::Code(&[code(scan!(SIG32_RETAIL_CENTISECS), 1, Operand::Absolute32)]),
+    Field::Code(&[code(scan!(SIG32_RETAIL_CENTISECS), 35, Operand::Absolute32)]),
+    Field::Code(&[code(scan!(SIG32_RETAIL_CENTISECS), 69, Operand::Absolute32)]),
+];
+
+const TIMER32_DECOMP: [Field; 3] = [
+    Field::Code(&[code(scan!(SIG32_DECOMP_CENTISECS), 2, Operand::Absolute32)]),
+    Field::Code(&[code(scan!(SIG32_DECOMP_CENTISECS), 29, Operand::Absolute32)]),
+    Field::Code(&[code(scan!(SIG32_DECOMP_CENTISECS), 51, Operand::Absolute32)]),
+];
+
+// Depending on the build, the stage timer code comes in two slightly different shapes
+const TIMER64_DECOMP: [Field; 3] = [
+    Field::Code(&[
+        code(scan!(SIG64_DECOMP_CENTISECS), 2, Operand::RipRelative),
+        code(scan!(SIG64_DECOMP_CENTISECS_ALT), 2, Operand::RipRelative),
+    ]),
+    Field::Code(&[
+        code(scan!(SIG64_DECOMP_CENTISECS), 29, Operand::RipRelative),
+        code(scan!(SIG64_DECOMP_CENTISECS_ALT), 31, Operand::RipRelative),
+    ]),
+    Field::Code(&[
+        code(scan!(SIG64_DECOMP_CENTISECS), 54, Operand::RipRelative),
+        code(scan!(SIG64_DECOMP_CENTISECS_ALT), 57, Operand::RipRelative),
+    ]),
+];
+
+const FIELDS64_DECOMP: Fields = fields64(
+    (10, Operand::RipRelative),
+    (12, Operand::RipRelative),
+    (15, Operand::ModuleRelative),
+    TIMER64_DECOMP,
+);
+
+const OBJECTS64_DECOMP: Option<Pointer> =
+    Some(code(scan!(SIG64_DECOMP_1_0_0_LEA), 3, Operand::RipRelative));
+
+const fn decomp64(detect: Scan, centisecs_bug: CentisecsBug) -> Layout {
+    Layout {
+        format: Format::Pe64,
+        detect,
+        centisecs_bug,
+        variables: code(detect, 4, Operand::ModuleRelative),
+        jump_table: JumpTable::ModuleRelative,
+        objects: OBJECTS64_DECOMP,
+        globals: variable(0x4 * 11, 15, Operand::ModuleRelative, 0),
+        fields: FIELDS64_DECOMP,
+    }
+}
+
+pub(crate) const fn layout(version: GameVersion) -> Layout {
+    match version {
+        GameVersion::Retail => Layout {
+            format: Format::Pe32,
+            detect: scan!(SIG32_RETAIL),
+            centisecs_bug: CentisecsBug::Always,
+            variables: code(scan!(SIG32_RETAIL), 3, Operand::Absolute32),
+            jump_table: JumpTable::Absolute32,
+            objects: None,
+            globals: variable(0x4 * 11, 16, Operand::Absolute32, 0),
+            fields: fields32(12, 18, 16, TIMER32_RETAIL),
+        },
+        GameVersion::Decompilation32bit1_0_0 => Layout {
+            format: Format::Pe32,
+            detect: scan!(SIG32_DECOMP_1_0_0),
+            centisecs_bug: CentisecsBug::UnlessFound(scan!(SIG32_DECOMP_TIMERBUG)),
+            variables: code(scan!(SIG32_DECOMP_1_0_0), 3, Operand::Absolute32),
+            jump_table: JumpTable::Absolute32,
+            objects: None,
+            globals: variable(0x4 * 11, 10, Operand::Absolute32, 0),
+            fields: fields32(8, 17, 10, TIMER32_DECOMP),
+        },
+        GameVersion::Decompilation32bit1_3_1 => Layout {
+            format: Format::Pe32,
+            detect: scan!(SIG32_DECOMP_1_3_1),
+            centisecs_bug: CentisecsBug::Never,
+            variables: code(scan!(SIG32_DECOMP_1_3_1), 3, Operand::Absolute32),
+            jump_table: JumpTable::Absolute32,
+            objects: None,
+            globals: variable(0x4 * 11, 10, Operand::Absolute32, 0),
+            fields: fields32(9, 17, 10, TIMER32_DECOMP),
+        },
+        GameVersion::Decompilation64bit1_0_0 => decomp64(
+            scan!(SIG64_DECOMP_1_0_0),
+            CentisecsBug::UnlessFound(scan!(SIG64_DECOMP_TIMERBUG)),
+        ),
+        GameVersion::Decompilation64bit1_3_1 => {
+            decomp64(scan!(SIG64_DECOMP_1_3_1), CentisecsBug::Never)
+        }
+        GameVersion::Decompilation64bit1_3_2 => {
+            decomp64(scan!(SIG64_DECOMP_1_3_2), CentisecsBug::Never)
+        }
+    }
+}
+
+/// Loose pattern matching the script variable switch of any build of the given format,
+/// along with plenty of unrelated code: every match is only a candidate.
+pub(crate) const fn generic_switch(format: Format) -> Option<Pointer> {
+    match format {
+        Format::Pe32 => Some(code(scan!(SIG32_GENERIC_SWITCH), 3, Operand::Absolute32)),
+        Format::Pe64 => Some(code(
+            scan!(SIG64_GENERIC_SWITCH),
+            4,
+            Operand::ModuleRelative,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Operand::{Absolute32 as Abs, ModuleRelative as Module, RipRelative as Rip};
+
+    /// A field, without its signatures
+    #[derive(PartialEq, Eq, Debug)]
+    enum Expected {
+        Variable(u32, u32, Operand, u32),
+        Object(u32),
+        Code(Vec<(u32, Operand)>),
+    }
+
+    fn describe(field: &Field) -> Expected {
+        match *field {
+            Field::Variable {
+                case,
+                operand,
+                encoding,
+                offset,
+            } => Expected::Variable(case, operand, encoding, offset),
+            Field::Object(offset) => Expected::Object(offset),
+            Field::Code(pointers) => Expected::Code(
+                pointers
+                    .iter()
+                    .map(|pointer| (pointer.offset, pointer.operand))
+                    .collect(),
+            ),
+            Field::Resolved(_) => unreachable!("layouts are never resolved"),
+        }
+    }
+
+    fn code(pointers: &[(u32, Operand)]) -> Expected {
+        Expected::Code(pointers.to_vec())
+    }
+
+    /// The pointer paths of each version before the layouts were introduced, in `Fields` order
+    fn baseline(version: GameVersion) -> [Expected; 12] {
+        use Expected::{Object, Variable};
+
+        // Everything but the stage timer is read through the variable switch on 32-bit builds
+        let fields32 = |stage: u32, objects: u32, globals: u32, timer: [Expected; 3]| {
+            let [centisecs, seconds, minutes] = timer;
+            [
+                Variable(0x4 * 11, globals, Abs, 0x1AC),
+                Variable(0x4 * 19, objects, Abs, 0x1078),
+                Variable(0x4 * 19, objects, Abs, 0x7F8),
+                Variable(0x4 * 37, objects, Abs, 0x7F8),
+                Variable(0x4 * 32, objects, Abs, 0x37C8),
+                Variable(0x4 * 32, objects, Abs, 0x380C),
+                Variable(0x4 * 120, stage, Abs, 0),
+                Variable(0x4 * 119, stage, Abs, 0),
+                Variable(0x4 * 121, 11, Abs, 0),
+                seconds,
+                minutes,
+                centisecs,
+            ]
+        };
+
+        match version {
+            GameVersion::Retail => fields32(
+                12,
+                18,
+                16,
+                [code(&[(1, Abs)]), code(&[(35, Abs)]), code(&[(69, Abs)])],
+            ),
+            GameVersion::Decompilation32bit1_0_0 => fields32(
+                8,
+                17,
+                10,
+                [code(&[(2, Abs)]), code(&[(29, Abs)]), code(&[(51, Abs)])],
+            ),
+            GameVersion::Decompilation32bit1_3_1 => fields32(
+                9,
+                17,
+                10,
+                [code(&[(2, Abs)]), code(&[(29, Abs)]), code(&[(51, Abs)])],
+            ),
+            GameVersion::Decompilation64bit1_0_0
+            | GameVersion::Decompilation64bit1_3_1
+            | GameVersion::Decompilation64bit1_3_2 => [
+                Variable(0x4 * 11, 15, Module, 0x1AC),
+                Object(0x10B2),
+                Object(0x832),
+                Object(0x814),
+                Object(0x37D0),
+                Object(0x3814),
+                Variable(0x4 * 120, 10, Rip, 0),
+                Variable(0x4 * 119, 10, Rip, 0),
+                Variable(0x4 * 121, 12, Rip, 0),
+                code(&[(29, Rip), (31, Rip)]),
+                code(&[(54, Rip), (57, Rip)]),
+                code(&[(2, Rip), (2, Rip)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn layouts_match_the_baseline_pointer_paths() {
+        for version in GameVersion::ALL {
+            let layout = layout(version);
+            let actual = layout.fields.all().map(describe);
+            assert_eq!(actual, baseline(version), "{}", version.name());
+
+            let is_64_bit = layout.format == Format::Pe64;
+            let (globals, variables, jump_table, objects) = match version {
+                GameVersion::Retail => ((16, Abs), (3, Abs), JumpTable::Absolute32, None),
+                _ if !is_64_bit => ((10, Abs), (3, Abs), JumpTable::Absolute32, None),
+                _ => (
+                    (15, Module),
+                    (4, Module),
+                    JumpTable::ModuleRelative,
+                    Some((3, Rip)),
+                ),
+            };
+            assert_eq!(
+                describe(&layout.globals),
+                Expected::Variable(0x4 * 11, globals.0, globals.1, 0),
+                "{}",
+                version.name()
+            );
+            assert_eq!(
+                (layout.variables.offset, layout.variables.operand),
+                variables
+            );
+            assert_eq!(layout.jump_table, jump_table);
+            assert_eq!(
+                layout
+                    .objects
+                    .map(|objects| (objects.offset, objects.operand)),
+                objects
+            );
+        }
+    }
+
+    #[test]
+    fn only_the_original_builds_have_the_centisecs_bug() {
+        for version in GameVersion::ALL {
+            let bug = match layout(version).centisecs_bug {
+                CentisecsBug::Always => "always",
+                CentisecsBug::Never => "never",
+                CentisecsBug::UnlessFound(_) => "unless fixed",
+            };
+            let expected = match version {
+                GameVersion::Retail => "always",
+                GameVersion::Decompilation32bit1_0_0 | GameVersion::Decompilation64bit1_0_0 => {
+                    "unless fixed"
+                }
+                _ => "never",
+            };
+            assert_eq!(bug, expected, "{}", version.name());
+        }
+    }
+}
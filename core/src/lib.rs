@@ -0,0 +1,35 @@
+//! Game model and splitting logic for Sonic CD (2011).
+//!
+//! Apart from attaching to the game and the signature scanning in [`Addresses::init`], nothing
+//! in this crate talks to the LiveSplit runtime, so it builds for the host as well and can be shared by the wasm
+//! autosplitter and native tools (eg. the trace replay tool).
+#![cfg_attr(not(test), no_std)]
+#![warn(
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::style,
+    clippy::undocumented_unsafe_blocks,
+    rust_2018_idioms
+)]
+
+pub mod acts;
+pub mod addresses;
+pub mod config;
+pub mod game_version;
+pub mod globals;
+mod layout;
+pub mod lss;
+pub mod memory;
+pub mod route;
+pub mod rules;
+pub mod trace;
+pub mod watchers;
+
+pub use acts::{Acts, Location, TimePeriod, Zone};
+pub use addresses::Addresses;
+pub use config::{Category, Config};
+pub use game_version::{GameVersion, PROCESS_NAMES};
+pub use route::{Route, RouteEvent};
+pub use rules::{game_time, is_loading, reset, split, split_route, start};
+pub use watchers::{update_loop, Watchers};
@@ -0,0 +1,58 @@
+//! Reading LiveSplit split files (.lss). Only the segment names are needed, so the XML is
+//! scanned for them rather than fully parsed.
+
+/// Names of the segments of a split file, in order.
+///
+/// Names are returned as written in the file, so characters escaped by XML stay escaped.
+pub fn segment_names(lss: &str) -> impl Iterator<Item = &str> + Clone {
+    let segments = lss
+        .find("<Segments>")
+        .and_then(|start| {
+            let segments = &lss[start..];
+            Some(&segments[..segments.find("</Segments>")?])
+        })
+        .unwrap_or_default();
+
+    // Segment names are the only `Name` elements inside `Segments`.
+    // An empty name is written as an empty element.
+    segments.split("<Name").skip(1).filter_map(|element| {
+        if element.trim_start().starts_with("/>") {
+            Some("")
+        } else {
+            let (name, _) = element.strip_prefix('>')?.split_once("</Name>")?;
+            Some(name.trim())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_segment_names() {
+        let cases: [(&str, &[&str]); 5] = [
+            (
+                "<Run><GameName>Sonic CD</GameName><Segments>\
+                 <Segment><Name>PP1</Name><Icon /></Segment>\
+                 <Segment><Name> Palmtree Panic 2 </Name></Segment>\
+                 </Segments></Run>",
+                &["PP1", "Palmtree Panic 2"],
+            ),
+            (
+                "<Segments><Segment><Name /></Segment><Segment><Name/></Segment></Segments>",
+                &["", ""],
+            ),
+            (
+                "<Segments><Segment><Name>Tidal &amp; Tempest</Name></Segment></Segments>",
+                &["Tidal &amp; Tempest"],
+            ),
+            ("<Run><Name>Not a segment</Name></Run>", &[]),
+            ("", &[]),
+        ];
+
+        for (lss, names) in cases {
+            assert!(segment_names(lss).eq(names.iter().copied()), "{lss:?}");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use asr::{Address, Process};
+
+/// Source of the raw values the splitter logic reads every tick.
+///
+/// `update_loop` only ever needs single bytes and 32-bit integers from the addresses
+/// resolved in `Addresses::init`, so this is all a memory source has to provide.
+/// A failed read is reported as `None`.
+pub trait Memory {
+    fn read_u8(&self, address: Address) -> Option<u8>;
+    fn read_u32(&self, address: Address) -> Option<u32>;
+}
+
+impl Memory for Process {
+    fn read_u8(&self, address: Address) -> Option<u8> {
+        self.read(address).ok()
+    }
+
+    fn read_u32(&self, address: Address) -> Option<u32> {
+        self.read(address).ok()
+    }
+}
+
+/// In-memory stand-in for the game process, holding up to `N` scripted bytes.
+///
+/// Values are stored byte by byte, little endian, so a `u32` written at an address
+/// can be read back either whole or one byte at a time, exactly like in the game.
+/// Reading any byte that was never written fails, the same way an unmapped address would.
+pub struct FakeMemory<const N: usize> {
+    cells: [(Address, u8); N],
+    len: usize,
+}
+
+impl<const N: usize> Default for FakeMemory<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FakeMemory<N> {
+    pub const fn new() -> Self {
+        Self {
+            cells: [(Address::NULL, 0); N],
+            len: 0,
+        }
+    }
+
+    pub fn write_u8(&mut self, address: Address, value: u8) {
+        if let Some(cell) = self.cells[..self.len]
+            .iter_mut()
+            .find(|(addr, _)| *addr == address)
+        {
+            cell.1 = value;
+        } else {
+            assert!(self.len < N, "FakeMemory is full");
+            self.cells[self.len] = (address, value);
+            self.len += 1;
+        }
+    }
+
+    pub fn write_u32(&mut self, address: Address, value: u32) {
+        for (i, byte) in value.to_le_bytes().into_iter().enumerate() {
+            self.write_u8(address + i as u64, byte);
+        }
+    }
+
+    /// Makes every read touching `address` fail, simulating a transient read error.
+    pub fn remove(&mut self, address: Address) {
+        if let Some(pos) = self.cells[..self.len]
+            .iter()
+            .position(|(addr, _)| *addr == address)
+        {
+            self.cells.copy_within(pos + 1..self.len, pos);
+            self.len -= 1;
+        }
+    }
+}
+
+impl<const N: usize> Memory for FakeMemory<N> {
+    fn read_u8(&self, address: Address) -> Option<u8> {
+        self.cells[..self.len]
+            .iter()
+            .find(|(addr, _)| *addr == address)
+            .map(|&(_, value)| value)
+    }
+
+    fn read_u32(&self, address: Address) -> Option<u32> {
+        let mut bytes = [0; 4];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = self.read_u8(address + i as u64)?;
+        }
+        Some(u32::from_le_bytes(bytes))
+    }
+}
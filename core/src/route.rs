@@ -0,0 +1,441 @@
+//! Split routes, for runs that don't visit the stages in the game's order (level select,
+//! practice routes, mods): the run splits on each event of the route in turn.
+//!
+//! A route is written as text, one event per line:
+//!
+//! ```text
+//! # Comments and blank lines are ignored
+//! enter PP2            # Palmtree Panic Act 2 loads
+//! enter CC1 past       # Collision Chaos Act 1 loads, or is warped to, in the Past
+//! enter special-stage
+//! tally QQ1            # The score tally starts at the end of Quartz Quadrant Act 1
+//! final-boss           # The final split of the category, after the final boss
+//! manual               # A split done by hand
+//! ```
+//!
+//! Zones are written with their usual two letter codes (PP, CC, TT, QQ, WW, SS, MM),
+//! and time periods as `present`, `past`, `good-future` or `bad-future`.
+
+use core::fmt;
+
+use crate::{acts::ZONES, lss, Acts, TimePeriod, Zone};
+
+/// Maximum number of events in a route
+pub const MAX_EVENTS: usize = 128;
+
+/// Something happening in the game that a route can split on
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RouteEvent {
+    /// Entering an act, in any time period unless one is given. With a time period,
+    /// warping to it inside the act counts as well.
+    Enter(Acts, Option<TimePeriod>),
+    /// The score tally starting at the end of an act
+    Tally(Acts),
+    /// The final split of the category: defeating the final boss in Metallic Madness Act 3 or,
+    /// with RTA-TB, the credits or the main menu loading after it. The other bosses can't be
+    /// told apart from the score tally that follows them: use `tally` for their Act 3 instead.
+    FinalBoss,
+    /// A split done by hand, never triggered by the autosplitter
+    Manual,
+}
+
+impl fmt::Display for RouteEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Enter(act, None) => write!(f, "enter {}", ActCode(act)),
+            Self::Enter(act, Some(time_period)) => write!(
+                f,
+                "enter {} {}",
+                ActCode(act),
+                time_period_code(time_period)
+            ),
+            Self::Tally(act) => write!(f, "tally {}", ActCode(act)),
+            Self::FinalBoss => f.write_str("final-boss"),
+            Self::Manual => f.write_str("manual"),
+        }
+    }
+}
+
+impl RouteEvent {
+    /// Parses a single event, as written in a route. Comments are not handled here.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next()?;
+
+        let event = if kind.eq_ignore_ascii_case("enter") {
+            let act = parse_act(fields.next()?)?;
+            let time_period = match fields.next() {
+                Some(time_period) => Some(parse_time_period(time_period)?),
+                None => None,
+            };
+            Self::Enter(act, time_period)
+        } else if kind.eq_ignore_ascii_case("tally") {
+            Self::Tally(parse_act(fields.next()?)?)
+        } else if kind.eq_ignore_ascii_case("final-boss") {
+            Self::FinalBoss
+        } else if kind.eq_ignore_ascii_case("manual") {
+            Self::Manual
+        } else {
+            return None;
+        };
+
+        fields.next().is_none().then_some(event)
+    }
+
+    /// Guesses the event ending a LiveSplit segment from its name.
+    ///
+    /// A segment named after an act ("PP1", "Palmtree Panic 2", "Palmtree Panic Act 2") ends
+    /// with the act's score tally, and one named after a zone ("Collision Chaos", "CC") ends
+    /// with its Act 3. Metallic Madness Act 3 ends with the final boss instead.
+    /// Subsplits without a zone ("-Act 1") use the zone of their group, if known.
+    pub fn from_segment_name(name: &str, group: Option<Zone>) -> Option<Self> {
+        let (words, len) = name_words(name)?;
+        let words = &words[..len];
+        if words.is_empty() {
+            return None;
+        }
+
+        let act = match words {
+            [code] => parse_act(code),
+            _ => None,
+        }
+        .or_else(|| {
+            let (zone, act) = ZONES
+                .into_iter()
+                .find_map(|zone| Some((zone, strip_zone(words, zone)?)))
+                .or_else(|| Some((group?, words)))?;
+            match act {
+                [] => Acts::from_zone_act(zone, 3),
+                [act] => Acts::from_zone_act(zone, act.parse().ok()?),
+                _ => None,
+            }
+        })?;
+
+        Some(match act {
+            Acts::MetallicMadnessAct3 => Self::FinalBoss,
+            act => Self::Tally(act),
+        })
+    }
+}
+
+/// Why a route could not be read
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RouteError {
+    /// The line (starting from 1) is not a valid event
+    InvalidLine(usize),
+    /// The route has more than [`MAX_EVENTS`] events
+    TooLong,
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "invalid event on line {line}"),
+            Self::TooLong => write!(f, "more than {MAX_EVENTS} events"),
+        }
+    }
+}
+
+/// The ordered events a run splits on: the event at index N ends split N.
+#[derive(Clone, Copy)]
+pub struct Route {
+    events: [RouteEvent; MAX_EVENTS],
+    len: usize,
+}
+
+impl Default for Route {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Route {
+    pub const fn new() -> Self {
+        Self {
+            events: [RouteEvent::Enter(Acts::TitleScreen, None); MAX_EVENTS],
+            len: 0,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, RouteError> {
+        let mut route = Self::new();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let event = RouteEvent::parse(line).ok_or(RouteError::InvalidLine(line_no + 1))?;
+            route.push(event)?;
+        }
+
+        Ok(route)
+    }
+
+    /// Builds the route from the segments of a LiveSplit split file (.lss), one event per
+    /// segment (see [`RouteEvent::from_segment_name`]).
+    ///
+    /// Segments that can't be mapped are reported with their index, and split by hand.
+    pub fn from_splits_file(
+        lss: &str,
+        mut unmapped: impl FnMut(usize, &str),
+    ) -> Result<Self, RouteError> {
+        let names = lss::segment_names(lss);
+        let mut route = Self::new();
+
+        for (index, name) in names.clone().enumerate() {
+            let event = RouteEvent::from_segment_name(name, group_zone(names.clone().skip(index)))
+                .unwrap_or_else(|| {
+                    unmapped(index, name);
+                    RouteEvent::Manual
+                });
+            route.push(event)?;
+        }
+
+        Ok(route)
+    }
+
+    pub fn push(&mut self, event: RouteEvent) -> Result<(), RouteError> {
+        let slot = self.events.get_mut(self.len).ok_or(RouteError::TooLong)?;
+        *slot = event;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// The event ending the given split, if the route goes that far
+    pub fn get(&self, split_index: usize) -> Option<RouteEvent> {
+        self.events().get(split_index).copied()
+    }
+
+    pub fn events(&self) -> &[RouteEvent] {
+        &self.events[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Formats an act the way it is written in routes
+struct ActCode(Acts);
+
+impl fmt::Display for ActCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.0.zone(), self.0.act_number()) {
+            (Some(zone), Some(act)) => write!(f, "{}{act}", zone.code()),
+            _ if self.0 == Acts::SpecialStage => f.write_str("special-stage"),
+            _ => f.write_str(self.0.name()),
+        }
+    }
+}
+
+/// The words following the zone's code or full name at the start of `words`
+fn strip_zone<'a, 'b>(words: &'a [&'b str], zone: Zone) -> Option<&'a [&'b str]> {
+    if let [code, rest @ ..] = words {
+        if code.eq_ignore_ascii_case(zone.code()) {
+            return Some(rest);
+        }
+    }
+
+    zone.name().split(' ').try_fold(words, |rest, name_word| {
+        let (word, rest) = rest.split_first()?;
+        word.eq_ignore_ascii_case(name_word).then_some(rest)
+    })
+}
+
+/// Zone of the subsplit group a segment belongs to. In LiveSplit, the subsplits of a group
+/// start with `-`, and the group ends with a segment starting with its name in braces:
+/// `{Palmtree Panic} Act 3`.
+fn group_zone<'a>(mut names: impl Iterator<Item = &'a str>) -> Option<Zone> {
+    let group = names.find(|name| !name.starts_with('-'))?;
+    let (group, _) = group.strip_prefix('{')?.split_once('}')?;
+
+    let (words, len) = name_words(group)?;
+
+    ZONES
+        .into_iter()
+        .find(|&zone| strip_zone(&words[..len], zone).is_some_and(<[_]>::is_empty))
+}
+
+/// The words of a segment name, without punctuation nor the words "act" and "zone".
+/// Names of more than 4 words are never a location.
+fn name_words(name: &str) -> Option<([&str; 4], usize)> {
+    let mut words = [""; 4];
+    let mut len = 0;
+
+    for word in name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .filter(|word| !word.eq_ignore_ascii_case("act") && !word.eq_ignore_ascii_case("zone"))
+    {
+        *words.get_mut(len)? = word;
+        len += 1;
+    }
+
+    Some((words, len))
+}
+
+fn parse_act(code: &str) -> Option<Acts> {
+    if code.eq_ignore_ascii_case("special-stage") {
+        return Some(Acts::SpecialStage);
+    }
+
+    let (zone, act) = code.split_at_checked(2)?;
+    Acts::from_zone_act(parse_zone(zone)?, act.parse().ok()?)
+}
+
+fn parse_zone(code: &str) -> Option<Zone> {
+    ZONES
+        .into_iter()
+        .find(|zone| zone.code().eq_ignore_ascii_case(code))
+}
+
+fn time_period_code(time_period: TimePeriod) -> &'static str {
+    match time_period {
+        TimePeriod::Present => "present",
+        TimePeriod::Past => "past",
+        TimePeriod::GoodFuture => "good-future",
+        TimePeriod::BadFuture => "bad-future",
+    }
+}
+
+fn parse_time_period(code: &str) -> Option<TimePeriod> {
+    [
+        TimePeriod::Present,
+        TimePeriod::Past,
+        TimePeriod::GoodFuture,
+        TimePeriod::BadFuture,
+    ]
+    .into_iter()
+    .find(|&time_period| time_period_code(time_period).eq_ignore_ascii_case(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_segment_names_to_events() {
+        use RouteEvent::{FinalBoss, Tally};
+
+        let cases = [
+            ("PP1", None, Some(Tally(Acts::PalmtreePanicAct1))),
+            (
+                "Palmtree Panic 2",
+                None,
+                Some(Tally(Acts::PalmtreePanicAct2)),
+            ),
+            (
+                "Palmtree Panic Act 2",
+                None,
+                Some(Tally(Acts::PalmtreePanicAct2)),
+            ),
+            (
+                "palmtree panic zone act 2",
+                None,
+                Some(Tally(Acts::PalmtreePanicAct2)),
+            ),
+            ("CC", None, Some(Tally(Acts::CollisionChaosAct3))),
+            (
+                "Collision Chaos",
+                None,
+                Some(Tally(Acts::CollisionChaosAct3)),
+            ),
+            ("MM3", None, Some(FinalBoss)),
+            ("Metallic Madness", None, Some(FinalBoss)),
+            (
+                "-Act 1",
+                Some(Zone::TidalTempest),
+                Some(Tally(Acts::TidalTempestAct1)),
+            ),
+            ("-Act 1", None, None),
+            ("Special Stage 1", None, None),
+            ("Special Stage 1", Some(Zone::PalmtreePanic), None),
+            ("Palmtree Panic Act 2 (Good Future)", None, None),
+            ("PP4", None, None),
+            ("", None, None),
+        ];
+
+        for (name, group, event) in cases {
+            assert_eq!(
+                RouteEvent::from_segment_name(name, group),
+                event,
+                "{name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_the_zone_of_subsplit_groups() {
+        let cases: [(&[&str], _); 5] = [
+            (
+                &["-Act 1", "-Act 2", "{Palmtree Panic} Act 3"],
+                Some(Zone::PalmtreePanic),
+            ),
+            (&["{CC} 3"], Some(Zone::CollisionChaos)),
+            (&["-Act 1", "Act 3"], None),
+            (&["-Act 1", "{Special Stages} 7"], None),
+            (&["-Act 1"], None),
+        ];
+
+        for (names, zone) in cases {
+            assert_eq!(group_zone(names.iter().copied()), zone, "{names:?}");
+        }
+    }
+
+    #[test]
+    fn splits_names_into_words() {
+        let cases: [(&str, Option<&[&str]>); 5] = [
+            ("Palmtree Panic Act 2", Some(&["Palmtree", "Panic", "2"])),
+            ("-Act 1", Some(&["1"])),
+            (
+                "Wacky Workbench Zone, Act 3!",
+                Some(&["Wacky", "Workbench", "3"]),
+            ),
+            ("", Some(&[])),
+            ("Palmtree Panic 2 Good Future", None),
+        ];
+
+        for (name, expected) in cases {
+            let words = name_words(name);
+            let words = words.as_ref().map(|(words, len)| &words[..*len]);
+            assert_eq!(words, expected, "{name:?}");
+        }
+    }
+
+    #[test]
+    fn builds_routes_from_split_files() {
+        let lss = "<Run><Segments>\
+            <Segment><Name>-Act 1</Name></Segment>\
+            <Segment><Name>{Palmtree Panic} Act 2</Name></Segment>\
+            <Segment><Name>Special Stage 1</Name></Segment>\
+            <Segment><Name /></Segment>\
+            <Segment><Name>MM3</Name></Segment>\
+            </Segments></Run>";
+
+        let mut unmapped = [None; 2];
+        let mut count = 0;
+        let route = Route::from_splits_file(lss, |index, _| {
+            unmapped[count] = Some(index);
+            count += 1;
+        })
+        .unwrap();
+
+        assert_eq!(
+            route.events(),
+            [
+                RouteEvent::Tally(Acts::PalmtreePanicAct1),
+                RouteEvent::Tally(Acts::PalmtreePanicAct2),
+                RouteEvent::Manual,
+                RouteEvent::Manual,
+                RouteEvent::FinalBoss,
+            ]
+        );
+        assert_eq!(unmapped, [Some(2), Some(3)]);
+    }
+}
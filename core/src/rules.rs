@@ -0,0 +1,796 @@
+use asr::time::Duration;
+
+use crate::{Acts, Config, Location, Route, RouteEvent, TimePeriod, Watchers};
+
+pub fn start(watchers: &Watchers, settings: &Config) -> bool {
+    if !watchers.is_valid {
+        return false;
+    }
+
+    if settings.category.individual_level() {
+        return settings.start && time_attack_start(watchers);
+    }
+
+    settings.start
+        && watchers
+            .level_id
+            .pair
+            .is_some_and(|act| act.current == Acts::MainMenu)
+        && watchers
+            .state
+            .pair
+            .is_some_and(|state| state.changed_from_to(&6, &7))
+}
+
+pub fn split(watchers: &Watchers, settings: &Config) -> bool {
+    if !watchers.is_valid {
+        return false;
+    }
+
+    if settings.category.individual_level() {
+        return time_attack_clear(watchers);
+    }
+
+    time_travel(watchers, settings)
+        || (settings.category.good_future() && good_future(watchers, settings))
+        || act_transition(watchers, settings)
+}
+
+/// Splits on the route's event for the current split, instead of following the act order.
+/// The other splits (time travel, Good Future, ...) are not used with a route.
+pub fn split_route(
+    watchers: &Watchers,
+    settings: &Config,
+    route: &Route,
+    split_index: usize,
+) -> bool {
+    if !watchers.is_valid {
+        return false;
+    }
+
+    if settings.category.individual_level() {
+        return time_attack_clear(watchers);
+    }
+
+    route
+        .get(split_index)
+        .is_some_and(|event| route_event(watchers, settings, event))
+}
+
+fn route_event(watchers: &Watchers, settings: &Config, event: RouteEvent) -> bool {
+    match event {
+        // The location, unlike the current act, changes when a Special Stage loads
+        RouteEvent::Enter(act, None) => watchers
+            .location
+            .pair
+            .is_some_and(|location| location.old.act != act && location.current.act == act),
+        RouteEvent::Enter(act, Some(time_period)) => {
+            watchers.location.pair.is_some_and(|location| {
+                location.changed_to(&Location {
+                    act,
+                    time_period: Some(time_period),
+                })
+            })
+        }
+        RouteEvent::Tally(act) => {
+            watchers
+                .score_tally
+                .pair
+                .is_some_and(|score_tally| score_tally.changed_from_to(&false, &true))
+                && watchers
+                    .level_id
+                    .pair
+                    .is_some_and(|level| level.current == act)
+        }
+        RouteEvent::FinalBoss => final_split(watchers, settings),
+        RouteEvent::Manual => false,
+    }
+}
+
+/// In the Good Future category, no split happens for an Act 3 played in the Bad Future (the
+/// zone's Good Future was missed), and the final split requires the Good Future boss.
+/// The act is still the one being left on the tick the next act loads, and the current one
+/// during its score tally, so this checks the act the split would be for.
+fn missed_good_future(watchers: &Watchers, settings: &Config) -> bool {
+    settings.category.good_future()
+        && watchers.location.pair.is_some_and(|location| {
+            (location.old.act.act_number() == Some(3)
+                && location.old.time_period == Some(TimePeriod::BadFuture))
+                || (location.old.act == Acts::MetallicMadnessAct3
+                    && location.old.time_period != Some(TimePeriod::GoodFuture))
+        })
+}
+
+/// In the Good Future category, reaching the Good Future of an Act 3 replaces the split of the
+/// zone's Act 2: both would happen on the same tick, when Act 3 loads
+fn splits_on_future_restored(act: Acts, settings: &Config) -> bool {
+    settings.category.good_future()
+        && act.act_number() == Some(2)
+        && act
+            .zone()
+            .is_some_and(|zone| settings.future_restored(zone))
+}
+
+/// Good Future category: reaching the Good Future of a zone's Act 3
+fn good_future(watchers: &Watchers, settings: &Config) -> bool {
+    watchers.location.pair.is_some_and(|location| {
+        location.old.act != location.current.act
+            && location.current.act.act_number() == Some(3)
+            && location.current.time_period == Some(TimePeriod::GoodFuture)
+            && location
+                .current
+                .zone()
+                .is_some_and(|zone| settings.future_restored(zone))
+    })
+}
+
+/// Warping to the Past or to the Future inside the same act
+fn time_travel(watchers: &Watchers, settings: &Config) -> bool {
+    watchers.location.pair.is_some_and(|location| {
+        location.old.act == location.current.act
+            && location.old.time_period != location.current.time_period
+            && match location.current.time_period {
+                Some(TimePeriod::Past) => settings.time_travel_past(location.current.act),
+                Some(time_period) if time_period.is_future() => {
+                    settings.time_travel_future(location.current.act)
+                }
+                _ => false,
+            }
+    })
+}
+
+fn act_transition(watchers: &Watchers, settings: &Config) -> bool {
+    if missed_good_future(watchers, settings) {
+        return false;
+    }
+
+    // The final split always depends on the boss, even when splitting on the score tally
+    if settings.split_on_tally
+        && watchers
+            .level_id
+            .pair
+            .is_some_and(|act| act.old != Acts::MetallicMadnessAct3)
+    {
+        return act_clear(watchers, settings);
+    }
+
+    if watchers
+        .level_id
+        .pair
+        .is_some_and(|act| splits_on_future_restored(act.old, settings))
+    {
+        return false;
+    }
+
+    watchers.level_id.pair.is_some_and(|act| match act.old {
+        Acts::PalmtreePanicAct1 => {
+            settings.palmtree_panic_1 && act.current == Acts::PalmtreePanicAct2
+        }
+        Acts::PalmtreePanicAct2 => {
+            settings.palmtree_panic_2 && act.current == Acts::PalmtreePanicAct3
+        }
+        Acts::PalmtreePanicAct3 => {
+            settings.palmtree_panic_3 && act.current == Acts::CollisionChaosAct1
+        }
+        Acts::CollisionChaosAct1 => {
+            settings.collision_chaos_1 && act.current == Acts::CollisionChaosAct2
+        }
+        Acts::CollisionChaosAct2 => {
+            settings.collision_chaos_2 && act.current == Acts::CollisionChaosAct3
+        }
+        Acts::CollisionChaosAct3 => {
+            settings.collision_chaos_3 && act.current == Acts::TidalTempestAct1
+        }
+        Acts::TidalTempestAct1 => settings.tidal_tempest_1 && act.current == Acts::TidalTempestAct2,
+        Acts::TidalTempestAct2 => settings.tidal_tempest_2 && act.current == Acts::TidalTempestAct3,
+        Acts::TidalTempestAct3 => {
+            settings.tidal_tempest_3 && act.current == Acts::QuartzQuadrantAct1
+        }
+        Acts::QuartzQuadrantAct1 => {
+            settings.quartz_quadrant_1 && act.current == Acts::QuartzQuadrantAct2
+        }
+        Acts::QuartzQuadrantAct2 => {
+            settings.quartz_quadrant_2 && act.current == Acts::QuartzQuadrantAct3
+        }
+        Acts::QuartzQuadrantAct3 => {
+            settings.quartz_quadrant_3 && act.current == Acts::WackyWorkbenchAct1
+        }
+        Acts::WackyWorkbenchAct1 => {
+            settings.wacky_workbench_1 && act.current == Acts::WackyWorkbenchAct2
+        }
+        Acts::WackyWorkbenchAct2 => {
+            settings.wacky_workbench_2 && act.current == Acts::WackyWorkbenchAct3
+        }
+        Acts::WackyWorkbenchAct3 => {
+            settings.wacky_workbench_3 && act.current == Acts::StardustSpeedwayAct1
+        }
+        Acts::StardustSpeedwayAct1 => {
+            settings.stardust_speedway_1 && act.current == Acts::StardustSpeedwayAct2
+        }
+        Acts::StardustSpeedwayAct2 => {
+            settings.stardust_speedway_2 && act.current == Acts::StardustSpeedwayAct3
+        }
+        Acts::StardustSpeedwayAct3 => {
+            settings.stardust_speedway_3 && act.current == Acts::MetallicMadnessAct1
+        }
+        Acts::MetallicMadnessAct1 => {
+            settings.metallic_madness_1 && act.current == Acts::MetallicMadnessAct2
+        }
+        Acts::MetallicMadnessAct2 => {
+            settings.metallic_madness_2 && act.current == Acts::MetallicMadnessAct3
+        }
+        Acts::MetallicMadnessAct3 => settings.metallic_madness_3 && final_split(watchers, settings),
+        _ => false,
+    })
+}
+
+/// The end of the run: the final boss' health reaching zero or, with RTA-TB, the credits or the
+/// main menu loading after the final boss
+fn final_split(watchers: &Watchers, settings: &Config) -> bool {
+    if settings.category.rta_tb() {
+        watchers.level_id.pair.is_some_and(|act| {
+            act.old == Acts::MetallicMadnessAct3
+                && (act.current == Acts::Credits || act.current == Acts::MainMenu)
+        }) && watchers
+            .final_boss_health
+            .pair
+            .is_some_and(|finalboss_hp| finalboss_hp.old.is_some_and(|val| val == 0))
+            && watchers
+                .igt
+                .pair
+                .is_some_and(|igt| igt.old != Duration::ZERO)
+    } else {
+        final_boss_defeated(watchers)
+    }
+}
+
+/// The final boss' health reaching zero
+fn final_boss_defeated(watchers: &Watchers) -> bool {
+    watchers
+        .final_boss_health
+        .pair
+        .is_some_and(|finalboss_hp| finalboss_hp.changed_from_to(&Some(1), &Some(0)))
+        && watchers
+            .igt
+            .pair
+            .is_some_and(|igt| igt.current != Duration::ZERO)
+}
+
+/// The score tally starting at the end of an act
+fn act_clear(watchers: &Watchers, settings: &Config) -> bool {
+    watchers
+        .score_tally
+        .pair
+        .is_some_and(|score_tally| score_tally.changed_from_to(&false, &true))
+        && watchers.level_id.pair.is_some_and(|act| {
+            act.current != Acts::MetallicMadnessAct3
+                && settings.act(act.current)
+                && !splits_on_future_restored(act.current, settings)
+        })
+}
+
+pub fn reset(watchers: &Watchers, settings: &Config) -> bool {
+    if !watchers.is_valid {
+        return false;
+    }
+
+    if settings.category.individual_level() {
+        return settings.reset && time_attack_retry(watchers);
+    }
+
+    settings.reset
+        && watchers
+            .level_id
+            .pair
+            .is_some_and(|act| act.current == Acts::MainMenu)
+        && watchers
+            .state
+            .pair
+            .is_some_and(|state| state.changed_to(&5))
+}
+
+/// The in-game timer starting inside a stage entered from the Time Attack menu
+fn time_attack_start(watchers: &Watchers) -> bool {
+    watchers.time_attack
+        && watchers
+            .location
+            .pair
+            .is_some_and(|location| location.current.zone().is_some())
+        && watchers
+            .timer_is_running
+            .pair
+            .is_some_and(|timer_is_running| timer_is_running.changed_from_to(&false, &true))
+}
+
+/// The score tally starting at the end of a Time Attack stage
+fn time_attack_clear(watchers: &Watchers) -> bool {
+    watchers.time_attack
+        && watchers
+            .score_tally
+            .pair
+            .is_some_and(|score_tally| score_tally.changed_from_to(&false, &true))
+}
+
+/// Going back to the Time Attack menu, or the stage being restarted (the in-game timer going
+/// back without leaving the act). The timer is held while stopped, so the first value read once
+/// it runs again may already be past zero.
+fn time_attack_retry(watchers: &Watchers) -> bool {
+    watchers.location.pair.is_some_and(|location| {
+        location.changed_to(&Location {
+            act: Acts::TimeAttack,
+            time_period: None,
+        }) || (location.old.act == location.current.act
+            && watchers.igt.pair.is_some_and(|igt| igt.decreased()))
+    })
+}
+
+pub fn is_loading(watchers: &Watchers, settings: &Config) -> Option<bool> {
+    Some(if settings.category.rta_tb() {
+        watchers.time_bonus_start_value != 0
+            && watchers
+                .time_bonus
+                .pair
+                .is_some_and(|time_bonus| time_bonus.current != watchers.time_bonus_start_value)
+    } else {
+        true
+    })
+}
+
+pub fn game_time(
+    watchers: &Watchers,
+    settings: &Config,
+    has_centisecs_bug: bool,
+) -> Option<Duration> {
+    if settings.category.rta_tb() {
+        None
+    } else {
+        let (Some(igt), Some(centisecs)) = (&watchers.igt.pair, &watchers.centisecs.pair) else {
+            return None;
+        };
+
+        Some(
+            igt.current + watchers.accumulated_igt - watchers.buffer_igt - watchers.igt_offset
+                + if has_centisecs_bug {
+                    centisecs.current
+                } else {
+                    Duration::ZERO
+                },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use asr::{time::Duration, timer::TimerState, Address};
+
+    use super::*;
+    use crate::{globals::GlobalVariables, memory::FakeMemory, trace::Frame, update_loop};
+    use crate::{Addresses, Category, GameVersion};
+
+    /// The game, with its values scripted at the resolved addresses, and a LiveSplit timer
+    /// driven the same way the autosplitter drives it.
+    struct Game {
+        memory: FakeMemory<64>,
+        addresses: Addresses,
+        watchers: Watchers,
+        config: Config,
+        route: Option<Route>,
+        timer: TimerState,
+        splits: usize,
+        resets: usize,
+    }
+
+    impl Game {
+        fn new(config: Config) -> Self {
+            let address = |index: u64| Address::new(0x1000 + index * 0x10);
+            let addresses = Addresses {
+                demo_mode: address(0),
+                state: address(1),
+                score_tally_state: address(2),
+                time_bonus: address(3),
+                bhp_good: address(4),
+                bhp_bad: address(5),
+                level_id: address(6),
+                level_id_type: address(7),
+                timer_is_running: address(8),
+                seconds: address(9),
+                minutes: address(10),
+                centisecs: address(11),
+                globals: GlobalVariables::at(address(12)),
+                has_centisecs_bug: false,
+                game_version: GameVersion::Retail,
+            };
+
+            let mut memory = FakeMemory::new();
+            for address in [
+                addresses.demo_mode,
+                addresses.state,
+                addresses.score_tally_state,
+                addresses.bhp_good,
+                addresses.bhp_bad,
+                addresses.level_id,
+                addresses.level_id_type,
+                addresses.timer_is_running,
+                addresses.seconds,
+                addresses.minutes,
+                addresses.centisecs,
+            ] {
+                memory.write_u8(address, 0);
+            }
+            memory.write_u32(addresses.time_bonus, 0);
+
+            Self {
+                memory,
+                addresses,
+                watchers: Watchers::default(),
+                config,
+                route: None,
+                timer: TimerState::NotRunning,
+                splits: 0,
+                resets: 0,
+            }
+        }
+
+        /// Loads the given combined level id (see `Location::from_level_id`)
+        fn set_level(&mut self, lid: u32) {
+            self.memory
+                .write_u8(self.addresses.level_id_type, (lid / 100) as u8);
+            self.memory
+                .write_u8(self.addresses.level_id, (lid % 100) as u8);
+        }
+
+        fn set_state(&mut self, state: u8) {
+            self.memory.write_u8(self.addresses.state, state);
+        }
+
+        /// Stage timer, running, at the given time (centiseconds in 1/60ths, like the game)
+        fn set_timer(&mut self, minutes: u8, seconds: u8, frames: u8) {
+            self.memory.write_u8(self.addresses.timer_is_running, 1);
+            self.memory.write_u8(self.addresses.minutes, minutes);
+            self.memory.write_u8(self.addresses.seconds, seconds);
+            self.memory.write_u8(self.addresses.centisecs, frames);
+        }
+
+        fn set_score_tally(&mut self, running: bool) {
+            self.memory
+                .write_u8(self.addresses.score_tally_state, running as u8);
+        }
+
+        fn stop_timer(&mut self) {
+            self.memory.write_u8(self.addresses.timer_is_running, 0);
+        }
+
+        fn set_final_boss_health(&mut self, health: u8) {
+            self.memory.write_u8(self.addresses.bhp_good, health);
+        }
+
+        /// Runs a tick of the autosplitter, then returns the number of splits so far
+        fn tick(&mut self) -> usize {
+            let frame = Frame::read(&self.memory, &self.addresses);
+            update_loop(&frame, false, &mut self.watchers, self.timer);
+
+            if [TimerState::Running, TimerState::Paused].contains(&self.timer) {
+                if reset(&self.watchers, &self.config) {
+                    self.watchers.reset_run();
+                    self.timer = TimerState::NotRunning;
+                    self.splits = 0;
+                    self.resets += 1;
+                } else if match &self.route {
+                    Some(route) => split_route(&self.watchers, &self.config, route, self.splits),
+                    None => split(&self.watchers, &self.config),
+                } {
+                    self.splits += 1;
+                }
+            }
+
+            if self.timer == TimerState::NotRunning && start(&self.watchers, &self.config) {
+                self.timer = TimerState::Running;
+            }
+
+            self.splits
+        }
+
+        fn game_time(&self) -> Option<Duration> {
+            game_time(&self.watchers, &self.config, false)
+        }
+
+        /// Starts a run from the main menu, then loads the given stage
+        fn start_run(&mut self, lid: u32) {
+            self.set_level(1);
+            self.set_state(6);
+            self.tick();
+            self.set_state(7);
+            self.tick();
+            assert_eq!(self.timer, TimerState::Running);
+            self.set_level(lid);
+            self.set_state(0);
+            self.tick();
+        }
+    }
+
+    const MAIN_MENU: u32 = 1;
+    const TIME_ATTACK: u32 = 2;
+    const CREDITS: u32 = 8;
+    const PALMTREE_PANIC_1: u32 = 100;
+    const PALMTREE_PANIC_1_PAST: u32 = 101;
+    const PALMTREE_PANIC_1_GOOD_FUTURE: u32 = 102;
+    const PALMTREE_PANIC_2: u32 = 104;
+    const PALMTREE_PANIC_3_GOOD_FUTURE: u32 = 108;
+    const PALMTREE_PANIC_3_BAD_FUTURE: u32 = 109;
+    const COLLISION_CHAOS_1: u32 = 110;
+    const METALLIC_MADNESS_3_GOOD_FUTURE: u32 = 168;
+    const SPECIAL_STAGE: u32 = 200;
+
+    #[test]
+    fn starts_when_confirming_the_character_selection() {
+        let mut game = Game::new(Config::default());
+        game.set_level(MAIN_MENU);
+        game.set_state(6);
+        game.tick();
+        assert_eq!(game.timer, TimerState::NotRunning);
+
+        game.set_state(7);
+        game.tick();
+        assert_eq!(game.timer, TimerState::Running);
+    }
+
+    #[test]
+    fn splits_when_the_next_act_loads() {
+        let mut game = Game::new(Config::default());
+        game.start_run(PALMTREE_PANIC_1);
+        assert_eq!(game.tick(), 0);
+
+        game.set_level(PALMTREE_PANIC_2);
+        assert_eq!(game.tick(), 1);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn splits_when_the_final_boss_is_defeated() {
+        let mut game = Game::new(Config::default());
+        game.start_run(METALLIC_MADNESS_3_GOOD_FUTURE);
+        game.set_timer(0, 30, 0);
+        game.set_final_boss_health(1);
+        assert_eq!(game.tick(), 0);
+
+        game.set_final_boss_health(0);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn splits_after_the_final_boss_with_rta_tb() {
+        let mut game = Game::new(Config {
+            category: Category::AllTimeStones,
+            ..Config::default()
+        });
+        game.start_run(METALLIC_MADNESS_3_GOOD_FUTURE);
+        game.set_timer(0, 30, 0);
+        game.set_final_boss_health(1);
+        game.tick();
+
+        // The run only ends once the act is left
+        game.set_final_boss_health(0);
+        assert_eq!(game.tick(), 0);
+
+        game.set_level(CREDITS);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn splits_when_travelling_to_the_past() {
+        let mut game = Game::new(Config {
+            palmtree_panic_1_past: true,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_level(PALMTREE_PANIC_1_PAST);
+        assert_eq!(game.tick(), 1);
+
+        // Travelling to the Future isn't enabled for the act
+        game.set_level(PALMTREE_PANIC_1_GOOD_FUTURE);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn splits_when_travelling_to_the_future() {
+        let mut game = Game::new(Config {
+            palmtree_panic_1_future: true,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_level(PALMTREE_PANIC_1_PAST);
+        assert_eq!(game.tick(), 0);
+        game.set_level(PALMTREE_PANIC_1_GOOD_FUTURE);
+        assert_eq!(game.tick(), 1);
+
+        // Leaving the act is still split on its own
+        game.set_level(PALMTREE_PANIC_2);
+        assert_eq!(game.tick(), 2);
+    }
+
+    #[test]
+    fn splits_when_the_score_tally_starts() {
+        let mut game = Game::new(Config {
+            split_on_tally: true,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_score_tally(true);
+        assert_eq!(game.tick(), 1);
+        assert_eq!(game.tick(), 1);
+
+        // The act was already split when its tally started
+        game.set_score_tally(false);
+        game.set_level(PALMTREE_PANIC_2);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn resets_when_starting_a_new_game() {
+        let mut game = Game::new(Config::default());
+        game.start_run(PALMTREE_PANIC_1);
+
+        game.set_level(MAIN_MENU);
+        game.tick();
+        game.set_state(5);
+        game.tick();
+        assert_eq!(game.timer, TimerState::NotRunning);
+    }
+
+    #[test]
+    fn accumulates_the_igt_of_every_act() {
+        let mut game = Game::new(Config::default());
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_timer(1, 0, 0);
+        game.tick();
+        assert_eq!(game.game_time(), Some(Duration::seconds(60)));
+
+        // The act timer goes back to zero when the next act loads
+        game.set_level(PALMTREE_PANIC_2);
+        game.set_timer(0, 0, 0);
+        game.tick();
+        assert_eq!(game.game_time(), Some(Duration::seconds(60)));
+
+        game.set_timer(0, 10, 30);
+        game.tick();
+        assert_eq!(game.game_time(), Some(Duration::milliseconds(70_500)));
+    }
+
+    #[test]
+    fn restarts_time_attack_attempts() {
+        let mut game = Game::new(Config {
+            category: Category::IndividualLevel,
+            ..Config::default()
+        });
+        game.set_level(TIME_ATTACK);
+        game.tick();
+        game.set_level(PALMTREE_PANIC_1);
+        game.tick();
+        game.set_timer(0, 0, 0);
+        game.tick();
+        assert_eq!(game.timer, TimerState::Running);
+
+        game.set_timer(0, 10, 0);
+        game.tick();
+
+        // The stage restarts: the timer stops, then runs again from zero, first read a frame late
+        game.stop_timer();
+        game.tick();
+        game.set_timer(0, 0, 1);
+        game.tick();
+        assert_eq!(game.resets, 1);
+        assert_eq!(game.timer, TimerState::Running);
+
+        // The previous attempt doesn't count towards the new one
+        game.tick();
+        assert_eq!(game.game_time(), Some(Duration::milliseconds(10)));
+    }
+
+    #[test]
+    fn splits_once_when_securing_a_good_future() {
+        let mut game = Game::new(Config {
+            category: Category::GoodFuture,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_2);
+        game.set_level(PALMTREE_PANIC_3_GOOD_FUTURE);
+        assert_eq!(game.tick(), 1);
+        game.set_level(COLLISION_CHAOS_1);
+        assert_eq!(game.tick(), 2);
+    }
+
+    #[test]
+    fn skips_bad_future_act_3_splits_in_the_good_future_category() {
+        let mut game = Game::new(Config {
+            category: Category::GoodFuture,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_3_BAD_FUTURE);
+        game.set_level(COLLISION_CHAOS_1);
+        assert_eq!(game.tick(), 0);
+
+        let mut game = Game::new(Config {
+            category: Category::GoodFuture,
+            split_on_tally: true,
+            ..Config::default()
+        });
+        game.start_run(PALMTREE_PANIC_3_BAD_FUTURE);
+        game.set_score_tally(true);
+        assert_eq!(game.tick(), 0);
+        game.set_score_tally(false);
+        game.set_level(COLLISION_CHAOS_1);
+        assert_eq!(game.tick(), 0);
+
+        game.start_run(PALMTREE_PANIC_2);
+        game.set_level(PALMTREE_PANIC_3_GOOD_FUTURE);
+        assert_eq!(game.tick(), 1);
+        game.set_score_tally(true);
+        assert_eq!(game.tick(), 2);
+    }
+
+    #[test]
+    fn skips_implausible_values() {
+        let mut game = Game::new(Config::default());
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_timer(0, 30, 0);
+        game.tick();
+
+        // Reallocated memory: the next act seems to load, along with an impossible timer
+        game.set_level(PALMTREE_PANIC_2);
+        game.set_timer(0, 200, 0);
+        assert_eq!(game.tick(), 0);
+        assert!(!game.watchers.is_valid);
+        assert_eq!(game.watchers.read_failures(), 1);
+
+        game.set_level(PALMTREE_PANIC_1);
+        game.set_timer(0, 31, 0);
+        assert_eq!(game.tick(), 0);
+        assert_eq!(game.game_time(), Some(Duration::seconds(31)));
+    }
+
+    #[test]
+    fn keeps_the_final_boss_health_when_it_cannot_be_read() {
+        let mut game = Game::new(Config::default());
+        game.start_run(METALLIC_MADNESS_3_GOOD_FUTURE);
+        game.set_timer(1, 0, 0);
+        game.set_final_boss_health(1);
+        game.tick();
+
+        game.memory.remove(game.addresses.bhp_good);
+        game.tick();
+        assert_eq!(game.watchers.final_boss_health(), Some(1));
+
+        game.set_final_boss_health(0);
+        assert_eq!(game.tick(), 1);
+    }
+
+    #[test]
+    fn splits_on_the_final_boss_route_event_with_rta_tb() {
+        let mut game = Game::new(Config {
+            category: Category::AllTimeStones,
+            ..Config::default()
+        });
+        game.route = Some(Route::parse("enter MM3\nfinal-boss").unwrap());
+        game.start_run(METALLIC_MADNESS_3_GOOD_FUTURE);
+        assert_eq!(game.tick(), 1);
+        game.set_timer(0, 30, 0);
+        game.set_final_boss_health(1);
+        game.tick();
+
+        game.set_final_boss_health(0);
+        assert_eq!(game.tick(), 1);
+        game.set_level(CREDITS);
+        assert_eq!(game.tick(), 2);
+    }
+
+    #[test]
+    fn splits_on_route_events() {
+        let mut game = Game::new(Config::default());
+        game.route = Some(Route::parse("enter special-stage\nenter PP2").unwrap());
+        game.start_run(PALMTREE_PANIC_1);
+        game.set_level(SPECIAL_STAGE);
+        assert_eq!(game.tick(), 1);
+        game.set_level(PALMTREE_PANIC_2);
+        assert_eq!(game.tick(), 2);
+    }
+}
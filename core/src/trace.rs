@@ -0,0 +1,187 @@
+use core::{fmt, str::FromStr};
+
+use crate::{memory::Memory, Addresses};
+
+const HEADER_TAG: &str = "soniccd-trace";
+const FRAME_TAG: &str = "soniccd-frame";
+const TRACE_VERSION: u32 = 5;
+
+/// Describes the game a trace was recorded from.
+///
+/// Printed once before the frames, as the same raw values can lead to different game times
+/// depending on whether the game has the centisecs bug.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Header {
+    pub has_centisecs_bug: bool,
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{HEADER_TAG} v{TRACE_VERSION} centisecs_bug={}",
+            self.has_centisecs_bug as u8
+        )
+    }
+}
+
+impl Header {
+    /// Parses a header line. Anything before the tag (eg. a log prefix) is ignored.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line[line.find(HEADER_TAG)? + HEADER_TAG.len()..].split_whitespace();
+
+        if fields.next()?.strip_prefix('v')?.parse::<u32>().ok()? != TRACE_VERSION {
+            return None;
+        }
+
+        let has_centisecs_bug = match fields.next()?.strip_prefix("centisecs_bug=")? {
+            "0" => false,
+            "1" => true,
+            _ => return None,
+        };
+
+        Some(Self { has_centisecs_bug })
+    }
+}
+
+/// Raw values read from the game during a single tick, before any interpretation.
+///
+/// `None` means the value could not be read. Serialized as a single line:
+/// the tag followed by the fields in declaration order, with `-` for failed reads.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Frame {
+    pub demo_mode: Option<u8>,
+    pub state: Option<u8>,
+    pub score_tally_state: Option<u8>,
+    pub level_id_type: Option<u8>,
+    pub level_id: Option<u8>,
+    pub timer_is_running: Option<u8>,
+    pub minutes: Option<u8>,
+    pub seconds: Option<u8>,
+    pub centisecs: Option<u8>,
+    pub time_bonus: Option<u32>,
+    pub bhp_good: Option<u8>,
+    pub bhp_bad: Option<u8>,
+}
+
+impl Frame {
+    pub fn read(game: &impl Memory, addresses: &Addresses) -> Self {
+        Self {
+            demo_mode: game.read_u8(addresses.demo_mode),
+            state: game.read_u8(addresses.state),
+            score_tally_state: game.read_u8(addresses.score_tally_state),
+            level_id_type: game.read_u8(addresses.level_id_type),
+            level_id: game.read_u8(addresses.level_id),
+            timer_is_running: game.read_u8(addresses.timer_is_running),
+            minutes: game.read_u8(addresses.minutes),
+            seconds: game.read_u8(addresses.seconds),
+            centisecs: game.read_u8(addresses.centisecs),
+            time_bonus: game.read_u32(addresses.time_bonus),
+            bhp_good: game.read_u8(addresses.bhp_good),
+            bhp_bad: game.read_u8(addresses.bhp_bad),
+        }
+    }
+
+    /// Whether the values look like the game's, rather than random or reallocated memory.
+    ///
+    /// Flags are only read as set or not (`> 0`), so any value of theirs is plausible.
+    pub fn is_plausible(&self) -> bool {
+        self.demo_mode.is_some()
+            && self.timer_is_running.is_some()
+            && self.level_id_type.is_some_and(|value| value <= 2)
+            && self.level_id.is_some_and(|value| value < 100)
+            && self.seconds.is_some_and(|value| value < 60)
+            && self.centisecs.is_some_and(|value| value < 60)
+    }
+
+    /// Parses a frame line. Anything before the tag (eg. a log prefix) is ignored.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line[line.find(FRAME_TAG)? + FRAME_TAG.len()..].split_whitespace();
+
+        let frame = Self {
+            demo_mode: parse_field(fields.next()?)?,
+            state: parse_field(fields.next()?)?,
+            score_tally_state: parse_field(fields.next()?)?,
+            level_id_type: parse_field(fields.next()?)?,
+            level_id: parse_field(fields.next()?)?,
+            timer_is_running: parse_field(fields.next()?)?,
+            minutes: parse_field(fields.next()?)?,
+            seconds: parse_field(fields.next()?)?,
+            centisecs: parse_field(fields.next()?)?,
+            time_bonus: parse_field(fields.next()?)?,
+            bhp_good: parse_field(fields.next()?)?,
+            bhp_bad: parse_field(fields.next()?)?,
+        };
+
+        match fields.next() {
+            None => Some(frame),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(FRAME_TAG)?;
+        write_field(f, self.demo_mode)?;
+        write_field(f, self.state)?;
+        write_field(f, self.score_tally_state)?;
+        write_field(f, self.level_id_type)?;
+        write_field(f, self.level_id)?;
+        write_field(f, self.timer_is_running)?;
+        write_field(f, self.minutes)?;
+        write_field(f, self.seconds)?;
+        write_field(f, self.centisecs)?;
+        write_field(f, self.time_bonus)?;
+        write_field(f, self.bhp_good)?;
+        write_field(f, self.bhp_bad)
+    }
+}
+
+fn write_field(f: &mut fmt::Formatter<'_>, value: Option<impl fmt::Display>) -> fmt::Result {
+    match value {
+        Some(value) => write!(f, " {value}"),
+        None => f.write_str(" -"),
+    }
+}
+
+fn parse_field<T: FromStr>(field: &str) -> Option<Option<T>> {
+    match field {
+        "-" => Some(None),
+        _ => Some(Some(field.parse().ok()?)),
+    }
+}
+
+/// Fixed-size buffer used to format trace lines without an allocator.
+pub struct LineBuffer {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl LineBuffer {
+    pub fn format(value: &impl fmt::Display) -> Self {
+        let mut line = Self {
+            buf: [0; 128],
+            len: 0,
+        };
+        let _ = fmt::write(&mut line, format_args!("{value}"));
+        line
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole `&str`s are ever copied in by `write_str`
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for LineBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use asr::{time::Duration, timer::TimerState, watcher::Watcher};
+
+use crate::{
+    acts::{Location, TimePeriod},
+    trace::Frame,
+    Acts,
+};
+
+#[derive(Default)]
+pub struct Watchers {
+    pub(crate) accumulated_igt: Duration,
+    pub(crate) buffer_igt: Duration,
+    pub(crate) igt_offset: Duration,
+    pub(crate) time_bonus_start_value: u32,
+    pub(crate) is_valid: bool,
+    pub(crate) read_failures: u32,
+    pub(crate) demo_mode: Watcher<bool>,
+    pub(crate) state: Watcher<u8>,
+    pub(crate) time_bonus: Watcher<u32>,
+    pub(crate) final_boss_health: Watcher<Option<u8>>,
+    pub(crate) level_id: Watcher<Acts>,
+    pub(crate) location: Watcher<Location>,
+    pub(crate) score_tally: Watcher<bool>,
+    pub(crate) time_attack: bool,
+    pub(crate) timer_is_running: Watcher<bool>,
+    pub(crate) igt: Watcher<Duration>,
+    pub(crate) centisecs: Watcher<Duration>,
+    pub(crate) livesplit_timer_state: Watcher<TimerState>,
+}
+
+impl Watchers {
+    /// Whether the values read on the last tick were valid. If not, the watchers still hold
+    /// the last valid values, and no decision should be taken on them.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    /// Prepares the watchers for a new game process while a run is in progress.
+    ///
+    /// Everything read from the previous process is dropped, except for the run-scoped state:
+    /// the game time so far is folded into the accumulated IGT, so it carries on once the
+    /// in-game timer restarts from zero.
+    pub fn resume(&mut self) {
+        let igt = self.igt.pair.map_or(Duration::ZERO, |igt| igt.current);
+
+        *self = Self {
+            accumulated_igt: self.accumulated_igt + igt - self.buffer_igt,
+            igt_offset: self.igt_offset,
+            time_attack: self.time_attack,
+            read_failures: self.read_failures,
+            ..Self::default()
+        };
+    }
+
+    /// Clears the run-scoped state, for a run reset and started again on the same tick (eg. when
+    /// retrying a Time Attack stage), which the timer state never shows as not running.
+    pub fn reset_run(&mut self) {
+        self.accumulated_igt = Duration::ZERO;
+        self.buffer_igt = Duration::ZERO;
+        self.igt_offset = Duration::ZERO;
+    }
+
+    /// Number of ticks skipped so far because some values could not be read, or made no sense
+    pub fn read_failures(&self) -> u32 {
+        self.read_failures
+    }
+
+    /// Current act and time period, as read from the game
+    pub fn location(&self) -> Option<Location> {
+        self.location.pair.map(|location| location.current)
+    }
+
+    /// In-game timer of the current act, as displayed by the game
+    pub fn igt(&self) -> Option<Duration> {
+        self.igt.pair.map(|igt| igt.current)
+    }
+
+    pub fn time_bonus(&self) -> Option<u32> {
+        self.time_bonus.pair.map(|time_bonus| time_bonus.current)
+    }
+
+    /// Health of the Metallic Madness boss, only known while fighting it
+    pub fn final_boss_health(&self) -> Option<u8> {
+        self.final_boss_health
+            .pair
+            .and_then(|final_boss_health| final_boss_health.current)
+    }
+}
+
+pub fn update_loop(
+    frame: &Frame,
+    has_centisecs_bug: bool,
+    watchers: &mut Watchers,
+    livesplit_timer_state: TimerState,
+) {
+    // Failed reads are never turned into values: the watchers keep holding the last values read,
+    // and no decision is taken until every value the splitting logic depends on can be read again
+    let (
+        Some(demo_mode),
+        Some(state),
+        Some(score_tally_state),
+        Some(level_id_type),
+        Some(level_id),
+        Some(timer_is_running),
+        Some(minutes),
+        Some(seconds),
+        Some(centisecs),
+        Some(time_bonus),
+    ) = (
+        frame.demo_mode,
+        frame.state,
+        frame.score_tally_state,
+        frame.level_id_type,
+        frame.level_id,
+        frame.timer_is_running,
+        frame.minutes,
+        frame.seconds,
+        frame.centisecs,
+        frame.time_bonus,
+    )
+    else {
+        watchers.is_valid = false;
+        watchers.read_failures += 1;
+        return;
+    };
+
+    // Values which make no sense (eg. read while the game reallocates its data) are skipped
+    // the same way. If this goes on, the autosplitter looks for the addresses again.
+    if !frame.is_plausible() {
+        watchers.is_valid = false;
+        watchers.read_failures += 1;
+        return;
+    }
+    watchers.is_valid = true;
+
+    // LiveSplit's timer state, defined inside a watcher in order to define some actions when the timer starts or resets
+    let timer_state = *watchers
+        .livesplit_timer_state
+        .update_infallible(livesplit_timer_state);
+
+    // Update standard values
+    watchers.demo_mode.update_infallible(demo_mode > 0);
+    watchers.state.update_infallible(state);
+    watchers
+        .timer_is_running
+        .update_infallible(timer_is_running > 0);
+
+    // Level ID
+    match score_tally_state {
+        0 => {
+            let lid = level_id_type as u32 * 100 + level_id as u32;
+            let location = Location::from_level_id(lid).unwrap_or(match &watchers.location.pair {
+                Some(x) => x.current,
+                _ => Location::default(),
+            });
+            watchers.location.update_infallible(location);
+
+            // Special Stages are entered at the end of an act: keep that act as the current one,
+            // so the act split still happens once the next act loads
+            let act = match location.act {
+                Acts::SpecialStage => match &watchers.level_id.pair {
+                    Some(x) => x.current,
+                    _ => Acts::PalmtreePanicAct1,
+                },
+                act => act,
+            };
+            watchers.level_id.update_infallible(act);
+
+            // Metallic Madness Act 3 has a different boss in the Good and the Bad Future.
+            // A failed read keeps the last health read, so the defeat isn't missed.
+            let held = watchers
+                .final_boss_health
+                .pair
+                .and_then(|final_boss_health| final_boss_health.current);
+            let final_boss_health = match (location.act, location.time_period) {
+                (Acts::MetallicMadnessAct3, Some(TimePeriod::GoodFuture)) => {
+                    frame.bhp_good.or(held)
+                }
+                (Acts::MetallicMadnessAct3, Some(TimePeriod::BadFuture)) => frame.bhp_bad.or(held),
+                _ => None,
+            };
+            watchers
+                .final_boss_health
+                .update_infallible(final_boss_health);
+        }
+        _ => {
+            let location = match &watchers.location.pair {
+                Some(x) => x.current,
+                _ => Location::default(),
+            };
+            watchers.location.update_infallible(location);
+            watchers
+                .level_id
+                .update_infallible(match &watchers.level_id.pair {
+                    Some(x) => x.current,
+                    _ => Acts::PalmtreePanicAct1,
+                });
+            watchers.final_boss_health.update_infallible(None);
+        }
+    };
+
+    watchers
+        .score_tally
+        .update_infallible(score_tally_state != 0);
+
+    // Stages entered from the Time Attack menu are played in Time Attack mode,
+    // until going back to the main menu
+    if let Some(location) = &watchers.location.pair {
+        match location.current.act {
+            Acts::TimeAttack => watchers.time_attack = true,
+            Acts::TitleScreen | Acts::MainMenu => watchers.time_attack = false,
+            _ => (),
+        }
+    }
+
+    // IGT logic
+    let Some(demo_mode) = &watchers.demo_mode.pair else {
+        return;
+    };
+    let Some(timer_is_running) = &watchers.timer_is_running.pair else {
+        return;
+    };
+
+    let centisecs = (centisecs as u64 * 100) / 60;
+    let centis = *watchers
+        .centisecs
+        .update_infallible(Duration::milliseconds(centisecs as i64 * 10));
+
+    let new_igt =
+        if demo_mode.current || demo_mode.old || timer_state.current == TimerState::NotRunning {
+            Duration::ZERO
+        } else if !timer_is_running.old && !timer_is_running.current {
+            match &watchers.igt.pair {
+                Some(x) => x.current,
+                _ => Duration::ZERO,
+            }
+        } else {
+            let mins = minutes as u64;
+            let secs = seconds as u64;
+            Duration::milliseconds(
+                (mins * 60000 + secs * 1000 + if has_centisecs_bug { 0 } else { centisecs } * 10)
+                    as i64,
+            )
+        };
+
+    // Reset the buffer IGT variables when the timer is stopped
+    if timer_state.current == TimerState::NotRunning {
+        watchers.reset_run();
+    }
+
+    let final_igt = watchers.igt.update_infallible(new_igt);
+
+    if final_igt.decreased() {
+        watchers.accumulated_igt += final_igt.old - watchers.buffer_igt;
+        watchers.buffer_igt = final_igt.current;
+    }
+
+    // Set the IGT offset when starting a new run, if the game has the centisecs bug
+    if has_centisecs_bug
+        && timer_state.changed_from_to(&TimerState::NotRunning, &TimerState::Running)
+    {
+        watchers.igt_offset = centis.current;
+    }
+
+    // Time bonus start value
+    let time_bonus = watchers.time_bonus.update_infallible(time_bonus);
+
+    if time_bonus.changed_from(&0) {
+        watchers.time_bonus_start_value = time_bonus.current
+    } else if time_bonus.current == 0 {
+        watchers.time_bonus_start_value = 0
+    }
+}
@@ -0,0 +1,288 @@
+//! Replays a frame trace recorded by the autosplitter ("Record frame trace" setting)
+//! through the splitting logic, printing every start, split, reset and game time decision.
+//!
+//! Usage: replay [options] <trace file>
+//!
+//! The trace file can be the whole LiveSplit log: lines without trace data are ignored.
+
+use std::{env, fs, process::ExitCode};
+
+use asr::{time::Duration, timer::TimerState};
+use soniccd_core::{
+    game_time, is_loading, reset, split, split_route, start,
+    trace::{Frame, Header},
+    update_loop, Category, Config, Route, Watchers,
+};
+
+fn main() -> ExitCode {
+    let mut config = Config::default();
+    let mut segments = None;
+    let mut route_path = None;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--split-on-tally" => config.split_on_tally = true,
+            "--no-start" => config.start = false,
+            "--no-reset" => config.reset = false,
+            "--category" => match args.next().as_deref().and_then(parse_category) {
+                Some(category) => config.category = category,
+                None => return usage(),
+            },
+            "--route" => match args.next() {
+                Some(path) => route_path = Some(path),
+                None => return usage(),
+            },
+            "--segments" => match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) => segments = Some(n),
+                None => return usage(),
+            },
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return usage(),
+        }
+    }
+
+    let Some(path) = path else {
+        return usage();
+    };
+
+    let trace = match fs::read_to_string(&path) {
+        Ok(trace) => trace,
+        Err(err) => {
+            eprintln!("Failed to read {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let route = match route_path.map(|path| read_route(&path)).transpose() {
+        Ok(route) => route,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut replay = Replay::new(config, route, segments);
+    let frames = replay.run(&trace);
+
+    println!(
+        "{frames} frames replayed ({} with failed reads), {} splits, final game time {}",
+        replay.watchers.read_failures(),
+        replay.split_index,
+        format_time(replay.game_time)
+    );
+
+    ExitCode::SUCCESS
+}
+
+const USAGE: &str = "\
+Usage: replay [options] <trace file>
+
+Options:
+    --category NAME     Category rules to apply: any, good-future, ats, 100 or il
+    --split-on-tally    Split on score tally start
+    --no-start          Disable auto start
+    --no-reset          Disable auto reset
+    --route FILE        Split on the events of a split route file, or on the
+                        segments of a LiveSplit split file (.lss)
+    --segments N        End the run after N splits";
+
+fn read_route(path: &str) -> Result<Route, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("Failed to read {path}: {err}"))?;
+
+    let route = match path.to_ascii_lowercase().ends_with(".lss") {
+        true => Route::from_splits_file(&text, |index, name| {
+            eprintln!("Segment {} ({name}) matches no location", index + 1)
+        }),
+        false => Route::parse(&text),
+    };
+    route.map_err(|err| format!("Invalid split route {path}: {err}"))
+}
+
+fn parse_category(name: &str) -> Option<Category> {
+    Some(match name {
+        "any" => Category::AnyPercent,
+        "good-future" => Category::GoodFuture,
+        "ats" => Category::AllTimeStones,
+        "100" => Category::HundredPercent,
+        "il" => Category::IndividualLevel,
+        _ => return None,
+    })
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+/// Stand-in for the LiveSplit timer, driven the same way the autosplitter drives it.
+struct Replay {
+    config: Config,
+    route: Option<Route>,
+    segments: Option<usize>,
+    watchers: Watchers,
+    timer_state: TimerState,
+    split_index: usize,
+    game_time: Duration,
+    is_loading: Option<bool>,
+    /// Every decision printed so far, with the line of the frame it was taken on
+    events: Vec<(usize, String)>,
+}
+
+impl Replay {
+    fn new(config: Config, route: Option<Route>, segments: Option<usize>) -> Self {
+        Self {
+            config,
+            route,
+            segments,
+            watchers: Watchers::default(),
+            timer_state: TimerState::NotRunning,
+            split_index: 0,
+            game_time: Duration::ZERO,
+            is_loading: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Replays every frame of the trace, returning the number of frames
+    fn run(&mut self, trace: &str) -> usize {
+        let mut has_centisecs_bug = false;
+        let mut frames = 0;
+
+        for (line_no, line) in trace.lines().enumerate() {
+            if let Some(header) = Header::parse(line) {
+                has_centisecs_bug = header.has_centisecs_bug;
+                println!(
+                    "{:>6}  trace start (centisecs bug: {has_centisecs_bug})",
+                    line_no + 1
+                );
+            } else if let Some(frame) = Frame::parse(line) {
+                self.tick(line_no + 1, &frame, has_centisecs_bug);
+                frames += 1;
+            }
+        }
+
+        frames
+    }
+
+    fn tick(&mut self, line_no: usize, frame: &Frame, has_centisecs_bug: bool) {
+        update_loop(
+            frame,
+            has_centisecs_bug,
+            &mut self.watchers,
+            self.timer_state,
+        );
+
+        if [TimerState::Running, TimerState::Paused].contains(&self.timer_state) {
+            self.update_loading(line_no);
+
+            if let Some(time) = game_time(&self.watchers, &self.config, has_centisecs_bug) {
+                self.game_time = time;
+            }
+
+            if reset(&self.watchers, &self.config) {
+                self.reset(line_no);
+            } else if self.split() {
+                self.split_index += 1;
+                self.print(line_no, &format!("split {}", self.split_index));
+                if self.segments == Some(self.split_index) {
+                    self.timer_state = TimerState::Ended;
+                }
+            }
+        }
+
+        if self.config.category.individual_level()
+            && self.timer_state == TimerState::Ended
+            && reset(&self.watchers, &self.config)
+        {
+            self.reset(line_no);
+        }
+
+        if self.timer_state == TimerState::NotRunning && start(&self.watchers, &self.config) {
+            self.timer_state = TimerState::Running;
+            self.print(line_no, "start");
+            self.is_loading = Some(true);
+            self.update_loading(line_no);
+        }
+    }
+
+    fn split(&self) -> bool {
+        match &self.route {
+            Some(route) => split_route(&self.watchers, &self.config, route, self.split_index),
+            None => split(&self.watchers, &self.config),
+        }
+    }
+
+    fn reset(&mut self, line_no: usize) {
+        self.print(line_no, "reset");
+        self.watchers.reset_run();
+        self.timer_state = TimerState::NotRunning;
+        self.split_index = 0;
+        self.game_time = Duration::ZERO;
+        self.is_loading = None;
+    }
+
+    fn update_loading(&mut self, line_no: usize) {
+        let Some(loading) = is_loading(&self.watchers, &self.config) else {
+            return;
+        };
+
+        if self.is_loading != Some(loading) {
+            self.is_loading = Some(loading);
+            self.print(
+                line_no,
+                match loading {
+                    true => "pause game time",
+                    false => "resume game time",
+                },
+            );
+        }
+    }
+
+    fn print(&mut self, line_no: usize, event: &str) {
+        println!(
+            "{line_no:>6}  {event:<18} game time {}",
+            format_time(self.game_time)
+        );
+        self.events.push((line_no, event.into()));
+    }
+}
+
+fn format_time(time: Duration) -> String {
+    let millis = time.whole_milliseconds();
+    format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_palmtree_panic() {
+        let mut replay = Replay::new(Config::default(), None, None);
+        let frames = replay.run(include_str!("../fixtures/palmtree_panic.trace"));
+        assert_eq!(frames, 26);
+
+        let events: Vec<_> = replay
+            .events
+            .iter()
+            .map(|(line_no, event)| (*line_no, event.as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (5, "start"),
+                (15, "split 1"),
+                (22, "split 2"),
+                (27, "split 3")
+            ]
+        );
+        assert_eq!(format_time(replay.game_time), "0:12.500");
+    }
+}